@@ -2,6 +2,7 @@
 pkgs.mkShell {
 	buildInputs = [
 		pkgs.vulkan-loader
+		pkgs.mesa # lavapipe, to render the golden image tests.
 		pkgs.cmake
 		pkgs.python3
 		pkgs.shaderc
@@ -0,0 +1,219 @@
+use std::{
+	sync::Arc,
+	hash::Hash,
+	collections::HashMap,
+	time::Instant
+};
+use super::{
+	Clip,
+	Transform
+};
+
+/// Playback of a clip.
+#[derive(Clone, Debug)]
+pub struct Playback {
+	clip: Arc<Clip>,
+
+	/// Current time in the clip, in seconds.
+	time: f32,
+
+	/// Playback speed multiplier.
+	///
+	/// Negative speeds play the clip backward.
+	speed: f32,
+
+	/// Blend weight.
+	weight: f32,
+
+	looping: bool
+}
+
+impl Playback {
+	/// Play the given clip once, from the start.
+	pub fn new(clip: &Arc<Clip>) -> Self {
+		Self {
+			clip: clip.clone(),
+			time: 0.0,
+			speed: 1.0,
+			weight: 1.0,
+			looping: false
+		}
+	}
+
+	pub fn with_speed(mut self, speed: f32) -> Self {
+		self.speed = speed;
+		self
+	}
+
+	pub fn with_weight(mut self, weight: f32) -> Self {
+		self.weight = weight;
+		self
+	}
+
+	/// Repeat the clip indefinitely.
+	pub fn looping(mut self) -> Self {
+		self.looping = true;
+		self
+	}
+
+	pub fn clip(&self) -> &Arc<Clip> {
+		&self.clip
+	}
+
+	/// Current time in the clip.
+	pub fn time(&self) -> f32 {
+		self.time
+	}
+
+	pub fn set_time(&mut self, time: f32) {
+		self.time = time
+	}
+
+	pub fn speed(&self) -> f32 {
+		self.speed
+	}
+
+	pub fn set_speed(&mut self, speed: f32) {
+		self.speed = speed
+	}
+
+	pub fn weight(&self) -> f32 {
+		self.weight
+	}
+
+	pub fn set_weight(&mut self, weight: f32) {
+		self.weight = weight
+	}
+
+	pub fn is_looping(&self) -> bool {
+		self.looping
+	}
+
+	/// Checks if the clip has been entirely played.
+	///
+	/// Looping playbacks never finish.
+	pub fn is_finished(&self) -> bool {
+		!self.looping && if self.speed < 0.0 {
+			self.time <= 0.0
+		} else {
+			self.time >= self.clip.duration()
+		}
+	}
+
+	/// Advance the playback by `dt` seconds, scaled by its speed.
+	///
+	/// Finished playbacks hold the first or last pose of the clip.
+	pub fn advance(&mut self, dt: f32) {
+		let duration = self.clip.duration();
+		self.time += dt * self.speed;
+
+		if self.looping && duration > 0.0 {
+			self.time = self.time.rem_euclid(duration)
+		} else {
+			self.time = self.time.max(0.0).min(duration)
+		}
+	}
+
+	/// Current transform.
+	pub fn sample(&self) -> Transform {
+		self.clip.sample(self.time)
+	}
+}
+
+/// Animation worker.
+///
+/// Plays clips on targets identified by `K`, such as scene object ids.
+/// During the cycle phase, every playback is advanced by the elapsed time and sampled,
+/// and the playbacks of each target are blended according to their weight.
+/// The resulting transforms are given to the `apply` function
+/// during the apply phase, so they can be written in the state.
+pub struct Animator<K, F> {
+	playbacks: HashMap<K, Vec<Playback>>,
+	last_update: Option<Instant>,
+	transforms: Vec<(K, Transform)>,
+	apply: F
+}
+
+impl<K: Clone + Eq + Hash, F> Animator<K, F> {
+	pub fn new(apply: F) -> Self {
+		Self {
+			playbacks: HashMap::new(),
+			last_update: None,
+			transforms: Vec::new(),
+			apply
+		}
+	}
+
+	/// Add a playback to the given target.
+	///
+	/// It is blended with the other playbacks of the target.
+	pub fn play(&mut self, target: K, playback: Playback) {
+		self.playbacks.entry(target).or_default().push(playback)
+	}
+
+	/// Stop every playback of the given target.
+	///
+	/// The target keeps its last transform.
+	pub fn stop(&mut self, target: &K) {
+		self.playbacks.remove(target);
+	}
+
+	/// Playbacks of the given target.
+	pub fn playbacks(&self, target: &K) -> &[Playback] {
+		match self.playbacks.get(target) {
+			Some(playbacks) => playbacks,
+			None => &[]
+		}
+	}
+
+	/// Playbacks of the given target.
+	pub fn playbacks_mut(&mut self, target: &K) -> Option<&mut Vec<Playback>> {
+		self.playbacks.get_mut(target)
+	}
+
+	/// Transforms computed during the last update.
+	pub fn transforms(&self) -> &[(K, Transform)] {
+		&self.transforms
+	}
+
+	/// Advance every playback by `dt` seconds and compute the targets transforms.
+	///
+	/// Called during the cycle phase with the time elapsed since the previous cycle.
+	/// Finished playbacks are sampled one last time, then removed:
+	/// their target keeps its last transform.
+	pub fn update(&mut self, dt: f32) {
+		self.transforms.clear();
+		for (target, playbacks) in &mut self.playbacks {
+			for playback in playbacks.iter_mut() {
+				playback.advance(dt)
+			}
+
+			let blended = Transform::blend(playbacks.iter().map(|p| (p.sample(), p.weight)));
+			if let Some(transform) = blended {
+				self.transforms.push((target.clone(), transform))
+			}
+
+			playbacks.retain(|p| !p.is_finished())
+		}
+
+		self.playbacks.retain(|_, playbacks| !playbacks.is_empty())
+	}
+}
+
+impl<T, K: Clone + Eq + Hash, F: FnMut(&mut T, &K, &Transform)> cycles::Worker<T> for Animator<K, F> {
+	fn cycle(&mut self, _: &T) {
+		let now = Instant::now();
+		let dt = match self.last_update.replace(now) {
+			Some(last_update) => (now - last_update).as_secs_f32(),
+			None => 0.0
+		};
+
+		self.update(dt)
+	}
+
+	fn apply(&mut self, state: &mut T) {
+		for (target, transform) in &self.transforms {
+			(self.apply)(state, target, transform)
+		}
+	}
+}
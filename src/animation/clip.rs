@@ -0,0 +1,63 @@
+use glam::{
+	Vec3,
+	Quat
+};
+use super::{
+	Track,
+	Transform
+};
+
+/// Animation clip.
+///
+/// Made of optional translation, rotation and scale tracks.
+/// Components without track keep their default value.
+#[derive(Clone, Debug, Default)]
+pub struct Clip {
+	translation: Option<Track<Vec3>>,
+	rotation: Option<Track<Quat>>,
+	scale: Option<Track<Vec3>>,
+	duration: f32
+}
+
+impl Clip {
+	/// Create an empty clip.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	pub fn with_translation(mut self, track: Track<Vec3>) -> Self {
+		self.duration = self.duration.max(track.end());
+		self.translation = Some(track);
+		self
+	}
+
+	pub fn with_rotation(mut self, track: Track<Quat>) -> Self {
+		self.duration = self.duration.max(track.end());
+		self.rotation = Some(track);
+		self
+	}
+
+	pub fn with_scale(mut self, track: Track<Vec3>) -> Self {
+		self.duration = self.duration.max(track.end());
+		self.scale = Some(track);
+		self
+	}
+
+	/// Duration of the clip, in seconds.
+	///
+	/// This is the time of the last keyframe of its tracks.
+	pub fn duration(&self) -> f32 {
+		self.duration
+	}
+
+	/// Transform at the given time.
+	pub fn sample(&self, time: f32) -> Transform {
+		let default = Transform::default();
+
+		Transform {
+			translation: self.translation.as_ref().map(|t| t.sample(time)).unwrap_or(default.translation),
+			rotation: self.rotation.as_ref().map(|t| t.sample(time)).unwrap_or(default.rotation),
+			scale: self.scale.as_ref().map(|t| t.sample(time)).unwrap_or(default.scale)
+		}
+	}
+}
@@ -0,0 +1,156 @@
+//! Keyframe animations.
+use glam::{
+	Vec3,
+	Vec4,
+	Quat,
+	Mat4
+};
+
+mod track;
+mod clip;
+mod animator;
+
+pub use track::{
+	Interpolation,
+	Keyframe,
+	Track
+};
+pub use clip::Clip;
+pub use animator::{
+	Playback,
+	Animator
+};
+
+/// Value that can be interpolated between keyframes.
+pub trait Interpolate: Copy {
+	/// Linear interpolation from `self` to `other`.
+	fn lerp(&self, other: &Self, t: f32) -> Self;
+
+	/// Cubic (Catmull-Rom) interpolation from `b` to `c`,
+	/// where `a` and `d` are the previous and next values.
+	fn cubic(a: &Self, b: &Self, c: &Self, d: &Self, t: f32) -> Self;
+}
+
+/// Catmull-Rom spline between `b` and `c`.
+fn catmull_rom(a: Vec4, b: Vec4, c: Vec4, d: Vec4, t: f32) -> Vec4 {
+	let t2 = t * t;
+	let t3 = t2 * t;
+
+	(b * 2.0 + (c - a) * t + (a * 2.0 - b * 5.0 + c * 4.0 - d) * t2 + (b * 3.0 - a - c * 3.0 + d) * t3) * 0.5
+}
+
+impl Interpolate for f32 {
+	fn lerp(&self, other: &f32, t: f32) -> f32 {
+		self + (other - self) * t
+	}
+
+	fn cubic(a: &f32, b: &f32, c: &f32, d: &f32, t: f32) -> f32 {
+		catmull_rom(Vec4::splat(*a), Vec4::splat(*b), Vec4::splat(*c), Vec4::splat(*d), t).x
+	}
+}
+
+impl Interpolate for Vec3 {
+	fn lerp(&self, other: &Vec3, t: f32) -> Vec3 {
+		Vec3::lerp(*self, *other, t)
+	}
+
+	fn cubic(a: &Vec3, b: &Vec3, c: &Vec3, d: &Vec3, t: f32) -> Vec3 {
+		catmull_rom(a.extend(0.0), b.extend(0.0), c.extend(0.0), d.extend(0.0), t).truncate()
+	}
+}
+
+/// Rotations are interpolated along the shortest path.
+impl Interpolate for Quat {
+	fn lerp(&self, other: &Quat, t: f32) -> Quat {
+		self.slerp(*other, t)
+	}
+
+	/// Component-wise spline, normalized.
+	fn cubic(a: &Quat, b: &Quat, c: &Quat, d: &Quat, t: f32) -> Quat {
+		let b = Vec4::from(*b);
+		let a = align(b, Vec4::from(*a));
+		let c = align(b, Vec4::from(*c));
+		let d = align(c, Vec4::from(*d));
+		Quat::from(catmull_rom(a, b, c, d, t).normalize())
+	}
+}
+
+/// Flip the quaternion `q` to the same hemisphere as `reference`.
+fn align(reference: Vec4, q: Vec4) -> Vec4 {
+	if reference.dot(q) < 0.0 {
+		-q
+	} else {
+		q
+	}
+}
+
+/// Decomposed transformation.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Transform {
+	pub translation: Vec3,
+	pub rotation: Quat,
+	pub scale: Vec3
+}
+
+impl Transform {
+	pub fn new(translation: Vec3, rotation: Quat, scale: Vec3) -> Self {
+		Self {
+			translation,
+			rotation,
+			scale
+		}
+	}
+
+	/// Transformation matrix (scale, then rotation, then translation).
+	pub fn matrix(&self) -> Mat4 {
+		Mat4::from_scale_rotation_translation(self.scale, self.rotation, self.translation)
+	}
+
+	/// Weighted blend of the given transforms.
+	///
+	/// Translations and scales are averaged,
+	/// and rotations are normalized averages of the aligned quaternions.
+	/// Returns `None` if the total weight is not positive.
+	pub fn blend<I: IntoIterator<Item=(Transform, f32)>>(transforms: I) -> Option<Transform> {
+		let mut total = 0.0;
+		let mut translation = Vec3::zero();
+		let mut rotation: Option<Vec4> = None;
+		let mut scale = Vec3::zero();
+
+		for (transform, weight) in transforms {
+			if weight <= 0.0 {
+				continue
+			}
+
+			total += weight;
+			translation += transform.translation * weight;
+			scale += transform.scale * weight;
+
+			let q = Vec4::from(transform.rotation);
+			rotation = Some(match rotation {
+				Some(sum) => sum + align(sum, q) * weight,
+				None => q * weight
+			});
+		}
+
+		if total > 0.0 {
+			Some(Transform {
+				translation: translation / total,
+				rotation: Quat::from(rotation.unwrap().normalize()),
+				scale: scale / total
+			})
+		} else {
+			None
+		}
+	}
+}
+
+impl Default for Transform {
+	fn default() -> Self {
+		Self {
+			translation: Vec3::zero(),
+			rotation: Quat::identity(),
+			scale: Vec3::one()
+		}
+	}
+}
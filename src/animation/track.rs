@@ -0,0 +1,102 @@
+use super::Interpolate;
+
+/// Interpolation between keyframes.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Interpolation {
+	/// Keeps the value of the previous keyframe.
+	Step,
+
+	/// Linear interpolation (spherical for rotations).
+	Linear,
+
+	/// Cubic (Catmull-Rom) interpolation, passing through every keyframe.
+	Cubic
+}
+
+impl Default for Interpolation {
+	fn default() -> Self {
+		Interpolation::Linear
+	}
+}
+
+/// Value at a given time, in seconds.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Keyframe<T> {
+	pub time: f32,
+	pub value: T
+}
+
+impl<T> Keyframe<T> {
+	pub fn new(time: f32, value: T) -> Self {
+		Self {
+			time,
+			value
+		}
+	}
+}
+
+/// Keyframe track.
+///
+/// Sampled values are clamped to the first and last keyframes.
+#[derive(Clone, Debug)]
+pub struct Track<T> {
+	keyframes: Vec<Keyframe<T>>,
+	interpolation: Interpolation
+}
+
+impl<T: Interpolate> Track<T> {
+	/// Create a new track.
+	///
+	/// The keyframes must be sorted by time, and there must be at least one.
+	pub fn new(interpolation: Interpolation, keyframes: Vec<Keyframe<T>>) -> Self {
+		assert!(!keyframes.is_empty(), "empty track");
+		assert!(keyframes.windows(2).all(|w| w[0].time <= w[1].time), "unsorted keyframes");
+
+		Self {
+			keyframes,
+			interpolation
+		}
+	}
+
+	pub fn keyframes(&self) -> &[Keyframe<T>] {
+		&self.keyframes
+	}
+
+	pub fn interpolation(&self) -> Interpolation {
+		self.interpolation
+	}
+
+	/// Time of the last keyframe.
+	pub fn end(&self) -> f32 {
+		self.keyframes.last().unwrap().time
+	}
+
+	/// Value at the given time.
+	pub fn sample(&self, time: f32) -> T {
+		let keyframes = &self.keyframes;
+
+		// Index of the first keyframe after `time`.
+		let next = keyframes.iter().position(|k| k.time > time).unwrap_or(keyframes.len());
+		if next == 0 {
+			return keyframes[0].value
+		}
+
+		if next == keyframes.len() {
+			return keyframes[next - 1].value
+		}
+
+		let b = &keyframes[next - 1];
+		let c = &keyframes[next];
+		let t = (time - b.time) / (c.time - b.time);
+
+		match self.interpolation {
+			Interpolation::Step => b.value,
+			Interpolation::Linear => b.value.lerp(&c.value, t),
+			Interpolation::Cubic => {
+				let a = &keyframes[(next - 1).saturating_sub(1)];
+				let d = &keyframes[std::cmp::min(next + 1, keyframes.len() - 1)];
+				T::cubic(&a.value, &b.value, &c.value, &d.value, t)
+			}
+		}
+	}
+}
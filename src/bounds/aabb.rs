@@ -0,0 +1,138 @@
+use glam::{
+	Vec3,
+	Mat4
+};
+use super::Sphere;
+
+/// Axis-aligned bounding box.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Aabb {
+	/// Minimum corner.
+	pub min: Vec3,
+
+	/// Maximum corner.
+	pub max: Vec3
+}
+
+impl Aabb {
+	pub fn new(min: Vec3, max: Vec3) -> Self {
+		Self {
+			min,
+			max
+		}
+	}
+
+	/// Create the box with the given center and half extents.
+	pub fn from_center(center: Vec3, half_extents: Vec3) -> Self {
+		Self {
+			min: center - half_extents,
+			max: center + half_extents
+		}
+	}
+
+	/// Smallest box containing all the given points.
+	///
+	/// Returns `None` if there are no points.
+	pub fn from_points<I: IntoIterator<Item=Vec3>>(points: I) -> Option<Self> {
+		let mut points = points.into_iter();
+		let first = points.next()?;
+		let mut aabb = Aabb::new(first, first);
+		for point in points {
+			aabb.extend(point)
+		}
+
+		Some(aabb)
+	}
+
+	/// Grow the box so that it contains the given point.
+	pub fn extend(&mut self, point: Vec3) {
+		self.min = self.min.min(point);
+		self.max = self.max.max(point)
+	}
+
+	/// Center of the box.
+	pub fn center(&self) -> Vec3 {
+		(self.min + self.max) * 0.5
+	}
+
+	/// Half of the box size along each axis.
+	pub fn half_extents(&self) -> Vec3 {
+		(self.max - self.min) * 0.5
+	}
+
+	/// The eight corners of the box.
+	pub fn corners(&self) -> [Vec3; 8] {
+		let (a, b) = (self.min, self.max);
+		[
+			Vec3::new(a.x, a.y, a.z),
+			Vec3::new(b.x, a.y, a.z),
+			Vec3::new(a.x, b.y, a.z),
+			Vec3::new(b.x, b.y, a.z),
+			Vec3::new(a.x, a.y, b.z),
+			Vec3::new(b.x, a.y, b.z),
+			Vec3::new(a.x, b.y, b.z),
+			Vec3::new(b.x, b.y, b.z)
+		]
+	}
+
+	/// Checks if the given point is inside the box.
+	pub fn contains(&self, point: Vec3) -> bool {
+		self.min.cmple(point).all() && point.cmple(self.max).all()
+	}
+
+	/// Checks if the given box is inside this box.
+	pub fn contains_aabb(&self, other: &Aabb) -> bool {
+		self.min.cmple(other.min).all() && other.max.cmple(self.max).all()
+	}
+
+	/// Checks if the given box intersects this box.
+	pub fn intersects_aabb(&self, other: &Aabb) -> bool {
+		self.min.cmple(other.max).all() && other.min.cmple(self.max).all()
+	}
+
+	/// Smallest box containing both boxes.
+	pub fn union(&self, other: &Aabb) -> Aabb {
+		Aabb {
+			min: self.min.min(other.min),
+			max: self.max.max(other.max)
+		}
+	}
+
+	/// Checks if the given sphere intersects this box.
+	pub fn intersects_sphere(&self, sphere: &Sphere) -> bool {
+		self.distance_squared(sphere.center) <= sphere.radius * sphere.radius
+	}
+
+	/// Smallest axis-aligned box containing this box once transformed.
+	pub fn transformed(&self, transformation: &Mat4) -> Aabb {
+		let center = transformation.transform_point3(self.center());
+		let h = self.half_extents();
+		let half_extents = transformation.x_axis.truncate().abs() * h.x
+			+ transformation.y_axis.truncate().abs() * h.y
+			+ transformation.z_axis.truncate().abs() * h.z;
+
+		Aabb::from_center(center, half_extents)
+	}
+
+	/// Box grown by the given margin in every direction.
+	pub fn grown(&self, margin: f32) -> Aabb {
+		Aabb {
+			min: self.min - Vec3::splat(margin),
+			max: self.max + Vec3::splat(margin)
+		}
+	}
+
+	/// Surface area of the box.
+	pub fn surface_area(&self) -> f32 {
+		let d = self.max - self.min;
+		2.0 * (d.x * d.y + d.y * d.z + d.z * d.x)
+	}
+
+	/// Squared distance between the box and the given point.
+	///
+	/// Zero if the point is inside the box.
+	pub fn distance_squared(&self, point: Vec3) -> f32 {
+		let d = (self.min - point).max(point - self.max).max(Vec3::zero());
+		d.length_squared()
+	}
+}
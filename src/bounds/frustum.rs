@@ -0,0 +1,93 @@
+use glam::{
+	Vec3,
+	Mat4
+};
+use super::{
+	Plane,
+	Sphere,
+	Aabb,
+	Obb,
+	Volume
+};
+
+/// View frustum.
+///
+/// Made of up to six planes whose normals point inside the frustum.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Frustum {
+	/// Left, right, bottom, top, near and far planes.
+	///
+	/// Degenerate planes are `None`, such as the far plane of an infinite projection
+	/// (or the near plane under reversed-Z), that does not bound the frustum.
+	pub planes: [Option<Plane>; 6]
+}
+
+impl Frustum {
+	/// Extract the frustum of the given view-projection matrix.
+	///
+	/// The projection is expected to map depth to `[0, 1]`, as Vulkan does.
+	/// Reversed-Z projections, mapping the near plane to `1`, are also supported.
+	pub fn from_matrix(view_projection: &Mat4) -> Self {
+		// Rows of the matrix.
+		let m = view_projection.transpose();
+		let (r0, r1, r2, r3) = (m.x_axis, m.y_axis, m.z_axis, m.w_axis);
+
+		Self {
+			planes: [
+				Plane::try_from_coefficients(r3 + r0), // left
+				Plane::try_from_coefficients(r3 - r0), // right
+				Plane::try_from_coefficients(r3 + r1), // bottom
+				Plane::try_from_coefficients(r3 - r1), // top
+				Plane::try_from_coefficients(r2), // near (far under reversed-Z)
+				Plane::try_from_coefficients(r3 - r2) // far (near under reversed-Z)
+			]
+		}
+	}
+
+	/// Non-degenerate planes.
+	fn iter(&self) -> impl Iterator<Item=&Plane> {
+		self.planes.iter().flatten()
+	}
+
+	/// Checks if the given point is inside the frustum.
+	pub fn contains(&self, point: Vec3) -> bool {
+		self.iter().all(|plane| plane.signed_distance(point) >= 0.0)
+	}
+
+	/// Checks if the given sphere intersects the frustum.
+	///
+	/// This test is conservative: it may report an intersection for
+	/// spheres close to the frustum corners.
+	pub fn intersects_sphere(&self, sphere: &Sphere) -> bool {
+		self.iter().all(|plane| plane.signed_distance(sphere.center) >= -sphere.radius)
+	}
+
+	/// Checks if the given box intersects the frustum.
+	///
+	/// This test is conservative: it may report an intersection for
+	/// boxes close to the frustum corners.
+	pub fn intersects_aabb(&self, aabb: &Aabb) -> bool {
+		self.iter().all(|plane| {
+			// Corner of the box the furthest along the plane normal.
+			let positive = Vec3::select(plane.normal.cmpge(Vec3::zero()), aabb.max, aabb.min);
+			plane.signed_distance(positive) >= 0.0
+		})
+	}
+
+	/// Checks if the given oriented box intersects the frustum.
+	///
+	/// This test is conservative: it may report an intersection for
+	/// boxes close to the frustum corners.
+	pub fn intersects_obb(&self, obb: &Obb) -> bool {
+		self.iter().all(|plane| plane.signed_distance(obb.center) >= -obb.projected_radius(plane))
+	}
+
+	/// Checks if the given bounding volume intersects the frustum.
+	pub fn intersects(&self, volume: &Volume) -> bool {
+		match volume {
+			Volume::Sphere(sphere) => self.intersects_sphere(sphere),
+			Volume::Aabb(aabb) => self.intersects_aabb(aabb),
+			Volume::Obb(obb) => self.intersects_obb(obb)
+		}
+	}
+}
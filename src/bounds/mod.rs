@@ -0,0 +1,82 @@
+//! Bounding volumes.
+use glam::{
+	Vec3,
+	Mat4
+};
+
+mod plane;
+mod sphere;
+mod aabb;
+mod obb;
+mod frustum;
+mod ray;
+
+pub use plane::Plane;
+pub use sphere::Sphere;
+pub use aabb::Aabb;
+pub use obb::Obb;
+pub use frustum::Frustum;
+pub use ray::Ray;
+
+/// Tolerance under which a length or determinant is considered null.
+const EPSILON: f32 = 1e-7;
+
+/// Bounding volume.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Volume {
+	Sphere(Sphere),
+	Aabb(Aabb),
+	Obb(Obb)
+}
+
+impl Volume {
+	/// Center of the volume.
+	pub fn center(&self) -> Vec3 {
+		match self {
+			Volume::Sphere(sphere) => sphere.center,
+			Volume::Aabb(aabb) => aabb.center(),
+			Volume::Obb(obb) => obb.center
+		}
+	}
+
+	/// Smallest axis-aligned box containing the volume.
+	pub fn aabb(&self) -> Aabb {
+		match self {
+			Volume::Sphere(sphere) => sphere.aabb(),
+			Volume::Aabb(aabb) => *aabb,
+			Volume::Obb(obb) => obb.aabb()
+		}
+	}
+
+	/// Volume containing this volume once transformed.
+	///
+	/// Axis-aligned boxes are transformed into oriented boxes.
+	pub fn transformed(&self, transformation: &Mat4) -> Volume {
+		match self {
+			Volume::Sphere(sphere) => Volume::Sphere(sphere.transformed(transformation)),
+			Volume::Aabb(aabb) => Volume::Obb(Obb::from_aabb(aabb, transformation)),
+			Volume::Obb(obb) => {
+				let local = Aabb::from_center(Vec3::zero(), obb.half_extents);
+				Volume::Obb(Obb::from_aabb(&local, &(*transformation * obb.local_to_world())))
+			}
+		}
+	}
+}
+
+impl From<Sphere> for Volume {
+	fn from(sphere: Sphere) -> Self {
+		Volume::Sphere(sphere)
+	}
+}
+
+impl From<Aabb> for Volume {
+	fn from(aabb: Aabb) -> Self {
+		Volume::Aabb(aabb)
+	}
+}
+
+impl From<Obb> for Volume {
+	fn from(obb: Obb) -> Self {
+		Volume::Obb(obb)
+	}
+}
@@ -0,0 +1,131 @@
+use glam::{
+	Vec3,
+	Mat4
+};
+use super::{
+	EPSILON,
+	Aabb,
+	Plane
+};
+
+/// Oriented bounding box.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Obb {
+	/// Center.
+	pub center: Vec3,
+
+	/// Unit axes of the box.
+	pub axes: [Vec3; 3],
+
+	/// Half of the box size along each axis.
+	pub half_extents: Vec3
+}
+
+impl Obb {
+	pub fn new(center: Vec3, axes: [Vec3; 3], half_extents: Vec3) -> Self {
+		Self {
+			center,
+			axes,
+			half_extents
+		}
+	}
+
+	/// Box obtained by transforming the given axis-aligned box.
+	///
+	/// The transformation is expected to be affine, without shearing.
+	/// Axes scaled to zero give a flat box, whose axes are still orthonormal.
+	pub fn from_aabb(aabb: &Aabb, transformation: &Mat4) -> Self {
+		let h = aabb.half_extents();
+		let mut axes = [
+			transformation.x_axis.truncate(),
+			transformation.y_axis.truncate(),
+			transformation.z_axis.truncate()
+		];
+		let scale = Vec3::new(axes[0].length(), axes[1].length(), axes[2].length());
+
+		// Bit `i` is set if axis `i` is scaled to zero.
+		let mut degenerate = 0u32;
+		for (i, axis) in axes.iter_mut().enumerate() {
+			if scale[i] > EPSILON {
+				*axis /= scale[i]
+			} else {
+				degenerate |= 1 << i
+			}
+		}
+
+		match degenerate.count_ones() {
+			0 => (),
+			1 => {
+				let i = degenerate.trailing_zeros() as usize;
+				axes[i] = axes[(i + 1) % 3].cross(axes[(i + 2) % 3]).normalize()
+			},
+			2 => {
+				let i = (!degenerate & 0b111).trailing_zeros() as usize;
+				let other = if axes[i].x.abs() < 0.9 { Vec3::unit_x() } else { Vec3::unit_y() };
+				let j = (i + 1) % 3;
+				axes[j] = axes[i].cross(other).normalize();
+				axes[(i + 2) % 3] = axes[i].cross(axes[j])
+			},
+			_ => axes = [Vec3::unit_x(), Vec3::unit_y(), Vec3::unit_z()]
+		}
+
+		Self {
+			center: transformation.transform_point3(aabb.center()),
+			axes,
+			half_extents: h * scale
+		}
+	}
+
+	/// Transformation from the box local space (where it is centered and axis-aligned) to world space.
+	pub fn local_to_world(&self) -> Mat4 {
+		Mat4::from_cols(
+			self.axes[0].extend(0.0),
+			self.axes[1].extend(0.0),
+			self.axes[2].extend(0.0),
+			self.center.extend(1.0)
+		)
+	}
+
+	/// The eight corners of the box.
+	pub fn corners(&self) -> [Vec3; 8] {
+		let x = self.axes[0] * self.half_extents.x;
+		let y = self.axes[1] * self.half_extents.y;
+		let z = self.axes[2] * self.half_extents.z;
+		let c = self.center;
+
+		[
+			c - x - y - z,
+			c + x - y - z,
+			c - x + y - z,
+			c + x + y - z,
+			c - x - y + z,
+			c + x - y + z,
+			c - x + y + z,
+			c + x + y + z
+		]
+	}
+
+	/// Smallest axis-aligned box containing this box.
+	pub fn aabb(&self) -> Aabb {
+		let half_extents = self.axes[0].abs() * self.half_extents.x
+			+ self.axes[1].abs() * self.half_extents.y
+			+ self.axes[2].abs() * self.half_extents.z;
+
+		Aabb::from_center(self.center, half_extents)
+	}
+
+	/// Checks if the given point is inside the box.
+	pub fn contains(&self, point: Vec3) -> bool {
+		let d = point - self.center;
+		d.dot(self.axes[0]).abs() <= self.half_extents.x
+			&& d.dot(self.axes[1]).abs() <= self.half_extents.y
+			&& d.dot(self.axes[2]).abs() <= self.half_extents.z
+	}
+
+	/// Radius of the box projected on the normal of the given plane.
+	pub(crate) fn projected_radius(&self, plane: &Plane) -> f32 {
+		plane.normal.dot(self.axes[0]).abs() * self.half_extents.x
+			+ plane.normal.dot(self.axes[1]).abs() * self.half_extents.y
+			+ plane.normal.dot(self.axes[2]).abs() * self.half_extents.z
+	}
+}
@@ -0,0 +1,73 @@
+use glam::{
+	Vec3,
+	Vec4
+};
+
+/// Plane.
+///
+/// Points `p` of the plane satisfy `normal.dot(p) + distance = 0`.
+/// The positive half-space is the one pointed by the normal.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Plane {
+	/// Unit normal vector.
+	pub normal: Vec3,
+
+	/// Signed distance from the origin to the plane, along the opposite of the normal.
+	pub distance: f32
+}
+
+impl Plane {
+	/// Create a new plane from its normal and distance to the origin.
+	///
+	/// The normal must be normalized.
+	pub fn new(normal: Vec3, distance: f32) -> Self {
+		Self {
+			normal,
+			distance
+		}
+	}
+
+	/// Create the plane with the given normal passing through the given point.
+	pub fn from_point_normal(point: Vec3, normal: Vec3) -> Self {
+		let normal = normal.normalize();
+		Self {
+			normal,
+			distance: -normal.dot(point)
+		}
+	}
+
+	/// Create a plane from its `(a, b, c, d)` equation coefficients,
+	/// where `ax + by + cz + d = 0`.
+	///
+	/// The coefficients do not need to be normalized,
+	/// but the normal `(a, b, c)` must not be null.
+	pub fn from_coefficients(coefficients: Vec4) -> Self {
+		Self::try_from_coefficients(coefficients).expect("degenerate plane")
+	}
+
+	/// Create a plane from its `(a, b, c, d)` equation coefficients,
+	/// where `ax + by + cz + d = 0`.
+	///
+	/// Returns `None` if the normal `(a, b, c)` is null (relatively to `d`),
+	/// for instance for the far plane of an infinite perspective projection.
+	pub fn try_from_coefficients(coefficients: Vec4) -> Option<Self> {
+		let normal = coefficients.truncate();
+		let len = normal.length();
+
+		if len <= std::f32::EPSILON * coefficients.w.abs() {
+			None
+		} else {
+			Some(Self {
+				normal: normal / len,
+				distance: coefficients.w / len
+			})
+		}
+	}
+
+	/// Signed distance between the plane and the given point.
+	///
+	/// Positive if the point is in the half-space pointed by the normal.
+	pub fn signed_distance(&self, point: Vec3) -> f32 {
+		self.normal.dot(point) + self.distance
+	}
+}
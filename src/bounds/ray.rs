@@ -0,0 +1,156 @@
+use glam::{
+	Vec3,
+	Mat4
+};
+use super::{
+	EPSILON,
+	Aabb,
+	Obb,
+	Sphere
+};
+
+/// Half-line.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Ray {
+	/// Origin.
+	pub origin: Vec3,
+
+	/// Unit direction.
+	pub direction: Vec3
+}
+
+impl Ray {
+	/// Create a new ray.
+	///
+	/// The direction is normalized.
+	pub fn new(origin: Vec3, direction: Vec3) -> Self {
+		Self {
+			origin,
+			direction: direction.normalize()
+		}
+	}
+
+	/// Point of the ray at the given distance from its origin.
+	pub fn at(&self, distance: f32) -> Vec3 {
+		self.origin + self.direction * distance
+	}
+
+	/// Ray transformed by the given matrix.
+	///
+	/// The direction is normalized again, so distances along the transformed ray
+	/// are measured in the transformed space.
+	pub fn transformed(&self, transformation: &Mat4) -> Ray {
+		Ray::new(
+			transformation.transform_point3(self.origin),
+			transformation.transform_vector3(self.direction)
+		)
+	}
+
+	/// Distance along the ray where it enters the given box, if it intersects it.
+	///
+	/// Returns `0.0` if the origin is inside the box.
+	pub fn intersect_aabb(&self, aabb: &Aabb) -> Option<f32> {
+		let mut near = 0.0f32;
+		let mut far = std::f32::INFINITY;
+
+		for axis in 0..3 {
+			let (origin, direction) = (self.origin[axis], self.direction[axis]);
+			let (min, max) = (aabb.min[axis], aabb.max[axis]);
+
+			if direction.abs() < EPSILON {
+				// Parallel to the slab: either always or never inside.
+				if origin < min || origin > max {
+					return None
+				}
+			} else {
+				let t0 = (min - origin) / direction;
+				let t1 = (max - origin) / direction;
+				near = near.max(t0.min(t1));
+				far = far.min(t0.max(t1));
+			}
+		}
+
+		if near <= far {
+			Some(near)
+		} else {
+			None
+		}
+	}
+
+	/// Distance along the ray where it enters the given oriented box, if it intersects it.
+	///
+	/// Returns `0.0` if the origin is inside the box.
+	pub fn intersect_obb(&self, obb: &Obb) -> Option<f32> {
+		// Express the ray in the box local space, where the box is axis-aligned.
+		let d = self.origin - obb.center;
+		let local = Ray {
+			origin: Vec3::new(d.dot(obb.axes[0]), d.dot(obb.axes[1]), d.dot(obb.axes[2])),
+			direction: Vec3::new(
+				self.direction.dot(obb.axes[0]),
+				self.direction.dot(obb.axes[1]),
+				self.direction.dot(obb.axes[2])
+			)
+		};
+
+		local.intersect_aabb(&Aabb::from_center(Vec3::zero(), obb.half_extents))
+	}
+
+	/// Distance along the ray where it enters the given sphere, if it intersects it.
+	///
+	/// Returns `0.0` if the origin is inside the sphere.
+	pub fn intersect_sphere(&self, sphere: &Sphere) -> Option<f32> {
+		let m = self.origin - sphere.center;
+		let b = m.dot(self.direction);
+		let c = m.length_squared() - sphere.radius * sphere.radius;
+
+		if c > 0.0 && b > 0.0 {
+			// Outside of the sphere, and pointing away from it.
+			return None
+		}
+
+		let discriminant = b * b - c;
+		if discriminant < 0.0 {
+			return None
+		}
+
+		Some((-b - discriminant.sqrt()).max(0.0))
+	}
+
+	/// Distance along the ray where it hits the given triangle, if it does.
+	///
+	/// Both faces of the triangle are considered.
+	/// Also returns the barycentric coordinates `(u, v)` of the hit point,
+	/// such that `point = (1 - u - v) * a + u * b + v * c`.
+	pub fn intersect_triangle(&self, a: Vec3, b: Vec3, c: Vec3) -> Option<(f32, f32, f32)> {
+		// Möller–Trumbore algorithm.
+		let ab = b - a;
+		let ac = c - a;
+		let p = self.direction.cross(ac);
+		let det = ab.dot(p);
+
+		if det.abs() < EPSILON {
+			// Parallel to the triangle.
+			return None
+		}
+
+		let inv_det = 1.0 / det;
+		let t = self.origin - a;
+		let u = t.dot(p) * inv_det;
+		if u < 0.0 || u > 1.0 {
+			return None
+		}
+
+		let q = t.cross(ab);
+		let v = self.direction.dot(q) * inv_det;
+		if v < 0.0 || u + v > 1.0 {
+			return None
+		}
+
+		let distance = ac.dot(q) * inv_det;
+		if distance >= 0.0 {
+			Some((distance, u, v))
+		} else {
+			None
+		}
+	}
+}
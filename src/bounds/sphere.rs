@@ -0,0 +1,85 @@
+use glam::{
+	Vec3,
+	Mat4
+};
+use super::Aabb;
+
+/// Bounding sphere.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Sphere {
+	/// Center.
+	pub center: Vec3,
+
+	/// Radius.
+	pub radius: f32
+}
+
+impl Sphere {
+	pub fn new(center: Vec3, radius: f32) -> Self {
+		Self {
+			center,
+			radius
+		}
+	}
+
+	/// Sphere containing all the given points.
+	///
+	/// The sphere is centered on the points bounding box,
+	/// hence it is not necessarily the smallest one.
+	/// Returns `None` if there are no points.
+	pub fn from_points<I: IntoIterator<Item=Vec3>>(points: I) -> Option<Self> where I::IntoIter: Clone {
+		let points = points.into_iter();
+		let center = Aabb::from_points(points.clone())?.center();
+		let radius_squared = points.map(|p| (p - center).length_squared()).fold(0.0f32, f32::max);
+
+		Some(Sphere::new(center, radius_squared.sqrt()))
+	}
+
+	/// Checks if the given point is inside the sphere.
+	pub fn contains(&self, point: Vec3) -> bool {
+		(point - self.center).length_squared() <= self.radius * self.radius
+	}
+
+	/// Checks if the given sphere intersects this sphere.
+	pub fn intersects_sphere(&self, other: &Sphere) -> bool {
+		let radius = self.radius + other.radius;
+		(other.center - self.center).length_squared() <= radius * radius
+	}
+
+	/// Checks if the given box intersects this sphere.
+	pub fn intersects_aabb(&self, aabb: &Aabb) -> bool {
+		aabb.intersects_sphere(self)
+	}
+
+	/// Smallest sphere containing both spheres.
+	pub fn union(&self, other: &Sphere) -> Sphere {
+		let offset = other.center - self.center;
+		let distance = offset.length();
+
+		if distance + other.radius <= self.radius {
+			*self
+		} else if distance + self.radius <= other.radius {
+			*other
+		} else {
+			let radius = (distance + self.radius + other.radius) * 0.5;
+			let center = self.center + offset * ((radius - self.radius) / distance);
+			Sphere::new(center, radius)
+		}
+	}
+
+	/// Sphere containing this sphere once transformed.
+	///
+	/// Non-uniform scales are accounted for by using the greatest scale factor.
+	pub fn transformed(&self, transformation: &Mat4) -> Sphere {
+		let scale = transformation.x_axis.truncate().length()
+			.max(transformation.y_axis.truncate().length())
+			.max(transformation.z_axis.truncate().length());
+
+		Sphere::new(transformation.transform_point3(self.center), self.radius * scale)
+	}
+
+	/// Smallest axis-aligned box containing the sphere.
+	pub fn aabb(&self) -> Aabb {
+		Aabb::from_center(self.center, Vec3::splat(self.radius))
+	}
+}
@@ -0,0 +1,72 @@
+use glam::{
+	Vec3,
+	Mat4
+};
+use crate::{
+	input::InputState,
+	camera::Camera
+};
+use super::{
+	Controller,
+	Settings,
+	Motion
+};
+
+/// First-person camera controller.
+///
+/// Like the [`Fly`](super::Fly) controller, but the camera walks on the horizontal plane:
+/// looking up or down does not change the movement direction,
+/// and there is no vertical movement.
+pub struct FirstPerson(Motion);
+
+impl FirstPerson {
+	/// Create a new controller at the given position (typically the eyes height),
+	/// looking in the given direction (yaw around the `z` axis, and pitch).
+	pub fn new(position: Vec3, yaw: f32, pitch: f32) -> Self {
+		Self(Motion::new(position, yaw, pitch))
+	}
+
+	/// Movement settings.
+	pub fn settings(&self) -> &Settings {
+		&self.0.settings
+	}
+
+	/// Movement settings.
+	pub fn settings_mut(&mut self) -> &mut Settings {
+		&mut self.0.settings
+	}
+
+	/// Current position.
+	pub fn position(&self) -> Vec3 {
+		self.0.position
+	}
+
+	/// Move the camera, for instance to follow the ground height.
+	pub fn set_position(&mut self, position: Vec3) {
+		self.0.position = position
+	}
+}
+
+impl Controller for FirstPerson {
+	fn update(&mut self, input: &InputState, dt: f32) {
+		self.0.look(input);
+
+		// Horizontal axes, without vertical movement.
+		let (right, forward, _) = super::axes(self.0.yaw, 0.0);
+		self.0.integrate(input, (right, forward, Vec3::zero()), dt)
+	}
+
+	fn transformation(&self) -> Mat4 {
+		self.0.transformation()
+	}
+}
+
+impl Camera for FirstPerson {
+	fn view(&self) -> Mat4 {
+		self.transformation().inverse()
+	}
+
+	fn position(&self) -> Vec3 {
+		self.0.position
+	}
+}
@@ -0,0 +1,70 @@
+use glam::{
+	Vec3,
+	Mat4
+};
+use crate::{
+	input::InputState,
+	camera::Camera
+};
+use super::{
+	Controller,
+	Settings,
+	Motion
+};
+
+/// Free-flying camera controller.
+///
+/// The camera moves in the direction it is looking at (`W`/`S`), sideways (`A`/`D`)
+/// and along its up axis (`E`/`Q`), and looks around with the mouse or a single touch point.
+/// Scrolling changes the speed.
+pub struct Fly(Motion);
+
+impl Fly {
+	/// Create a new controller at the given position,
+	/// looking in the given direction (yaw around the `z` axis, and pitch).
+	pub fn new(position: Vec3, yaw: f32, pitch: f32) -> Self {
+		Self(Motion::new(position, yaw, pitch))
+	}
+
+	/// Movement settings.
+	pub fn settings(&self) -> &Settings {
+		&self.0.settings
+	}
+
+	/// Movement settings.
+	pub fn settings_mut(&mut self) -> &mut Settings {
+		&mut self.0.settings
+	}
+
+	/// Current position.
+	pub fn position(&self) -> Vec3 {
+		self.0.position
+	}
+
+	/// Current velocity.
+	pub fn velocity(&self) -> Vec3 {
+		self.0.velocity
+	}
+}
+
+impl Controller for Fly {
+	fn update(&mut self, input: &InputState, dt: f32) {
+		self.0.look(input);
+		let axes = super::axes(self.0.yaw, self.0.pitch);
+		self.0.integrate(input, axes, dt)
+	}
+
+	fn transformation(&self) -> Mat4 {
+		self.0.transformation()
+	}
+}
+
+impl Camera for Fly {
+	fn view(&self) -> Mat4 {
+		self.transformation().inverse()
+	}
+
+	fn position(&self) -> Vec3 {
+		self.0.position
+	}
+}
@@ -0,0 +1,279 @@
+//! Camera controllers.
+use std::time::Instant;
+use glam::{
+	Vec2,
+	Vec3,
+	Mat4
+};
+use crate::input::{
+	Input,
+	InputState,
+	Key,
+	MouseButton
+};
+
+mod fly;
+mod first_person;
+
+pub use fly::Fly;
+pub use first_person::FirstPerson;
+
+/// Camera controller.
+pub trait Controller {
+	/// Update the camera according to the given input state,
+	/// and the time elapsed (in seconds) since the last update.
+	fn update(&mut self, input: &InputState, dt: f32);
+
+	/// Camera transformation (camera to world space).
+	fn transformation(&self) -> Mat4;
+}
+
+/// Movement parameters shared by the controllers.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Settings {
+	/// Maximum speed, in units per second.
+	pub speed: f32,
+
+	/// Speed multiplier applied while the boost key is pressed.
+	pub boost: f32,
+
+	/// Acceleration, in units per second squared.
+	///
+	/// An infinite acceleration makes the camera reach its speed instantly.
+	pub acceleration: f32,
+
+	/// Speed multiplier applied per line scrolled up (and divisor per line scrolled down).
+	///
+	/// A factor of `1.0` disables the scroll speed control.
+	pub scroll_factor: f32,
+
+	/// Rotation angle (in radians) per pixel of mouse or touch movement.
+	pub sensitivity: f32,
+
+	/// Button that must be held to look around, or `None` to always look around.
+	pub look_button: Option<MouseButton>
+}
+
+impl Default for Settings {
+	fn default() -> Self {
+		Self {
+			speed: 5.0,
+			boost: 4.0,
+			acceleration: 20.0,
+			scroll_factor: 1.2,
+			sensitivity: 0.002,
+			look_button: None
+		}
+	}
+}
+
+impl Settings {
+	/// Checks if the camera should follow the mouse movements.
+	fn is_looking(&self, input: &InputState) -> bool {
+		match self.look_button {
+			Some(button) => input.is_button_pressed(button),
+			None => true
+		}
+	}
+
+	/// Movement to look around, in pixels.
+	///
+	/// Dragging a single touch point always looks around,
+	/// as does the mouse while the look button is held.
+	fn look_delta(&self, input: &InputState) -> Vec2 {
+		let mut delta = if self.is_looking(input) {
+			input.mouse_delta()
+		} else {
+			Vec2::zero()
+		};
+
+		let mut touches = input.touches();
+		if let (Some(touch), None) = (touches.next(), touches.next()) {
+			delta += touch.delta
+		}
+
+		delta
+	}
+
+	/// Change the speed according to the scroll.
+	fn scroll_speed(&mut self, input: &InputState) {
+		self.speed *= self.scroll_factor.powf(input.scroll().y)
+	}
+
+	/// Maximum speed, given the current input.
+	fn max_speed(&self, input: &InputState) -> f32 {
+		if input.is_key_pressed(Key::Shift) {
+			self.speed * self.boost
+		} else {
+			self.speed
+		}
+	}
+}
+
+/// Movement direction requested by the keyboard, in camera space (`x` right, `y` forward, `z` up).
+///
+/// The result is not normalized.
+fn direction(input: &InputState) -> Vec3 {
+	let axis = |positive: &[Key], negative: &[Key]| {
+		let p = positive.iter().any(|k| input.is_key_pressed(*k));
+		let n = negative.iter().any(|k| input.is_key_pressed(*k));
+		(p as i32 - n as i32) as f32
+	};
+
+	Vec3::new(
+		axis(&[Key::D, Key::Right], &[Key::A, Key::Left]),
+		axis(&[Key::W, Key::Up], &[Key::S, Key::Down]),
+		axis(&[Key::E, Key::Space], &[Key::Q, Key::Control])
+	)
+}
+
+/// Move the velocity toward the target velocity, with the given acceleration.
+fn accelerate(velocity: Vec3, target: Vec3, acceleration: f32, dt: f32) -> Vec3 {
+	if dt <= 0.0 {
+		return velocity
+	}
+
+	let delta = target - velocity;
+	let max = acceleration * dt;
+	let len = delta.length();
+
+	if len <= max {
+		target
+	} else {
+		velocity + delta * (max / len)
+	}
+}
+
+/// Camera orientation from yaw (around the world `z` axis) and pitch angles.
+///
+/// Returns the camera `(right, forward, up)` axes.
+fn axes(yaw: f32, pitch: f32) -> (Vec3, Vec3, Vec3) {
+	let forward = Vec3::new(pitch.cos() * yaw.cos(), pitch.cos() * yaw.sin(), pitch.sin());
+	let right = Vec3::new(yaw.sin(), -yaw.cos(), 0.0);
+	let up = right.cross(forward);
+	(right, forward, up)
+}
+
+/// Camera transformation from its position and axes.
+fn transformation(position: Vec3, (right, forward, up): (Vec3, Vec3, Vec3)) -> Mat4 {
+	// The camera looks toward `-z`.
+	Mat4::from_cols(
+		right.extend(0.0),
+		up.extend(0.0),
+		(-forward).extend(0.0),
+		position.extend(1.0)
+	)
+}
+
+/// Maximum pitch angle, to avoid flipping over.
+const MAX_PITCH: f32 = std::f32::consts::FRAC_PI_2 - 1e-3;
+
+/// Camera motion shared by the controllers.
+///
+/// The controllers only differ by the basis in which the keyboard direction is expressed.
+struct Motion {
+	position: Vec3,
+	velocity: Vec3,
+	yaw: f32,
+	pitch: f32,
+	settings: Settings
+}
+
+impl Motion {
+	fn new(position: Vec3, yaw: f32, pitch: f32) -> Self {
+		Self {
+			position,
+			velocity: Vec3::zero(),
+			yaw,
+			pitch: pitch.max(-MAX_PITCH).min(MAX_PITCH),
+			settings: Settings::default()
+		}
+	}
+
+	/// Look around and change the speed according to the input.
+	fn look(&mut self, input: &InputState) {
+		let delta = self.settings.look_delta(input) * self.settings.sensitivity;
+		self.yaw -= delta.x;
+		self.pitch = (self.pitch - delta.y).max(-MAX_PITCH).min(MAX_PITCH);
+		self.settings.scroll_speed(input)
+	}
+
+	/// Accelerate toward the keyboard direction expressed in the given `(right, forward, up)` basis,
+	/// and move for `dt` seconds.
+	fn integrate(&mut self, input: &InputState, (right, forward, up): (Vec3, Vec3, Vec3), dt: f32) {
+		let direction = direction(input);
+		let direction = right * direction.x + forward * direction.y + up * direction.z;
+
+		let target = if direction == Vec3::zero() {
+			Vec3::zero()
+		} else {
+			direction.normalize() * self.settings.max_speed(input)
+		};
+
+		self.velocity = accelerate(self.velocity, target, self.settings.acceleration, dt);
+		self.position += self.velocity * dt
+	}
+
+	/// Camera transformation (camera to world space).
+	fn transformation(&self) -> Mat4 {
+		transformation(self.position, axes(self.yaw, self.pitch))
+	}
+}
+
+/// Worker updating a camera with a controller.
+///
+/// During the cycle phase, the controller is updated with the shared input state,
+/// whose per-frame movements are then reset.
+/// The resulting camera transformation is given to the `apply` function
+/// during the apply phase, so it can be written in the state.
+pub struct Worker<C, F> {
+	controller: C,
+	input: Input,
+	last_update: Option<Instant>,
+	transformation: Mat4,
+	apply: F
+}
+
+impl<C: Controller, F> Worker<C, F> {
+	pub fn new(controller: C, input: Input, apply: F) -> Self {
+		let transformation = controller.transformation();
+
+		Self {
+			controller,
+			input,
+			last_update: None,
+			transformation,
+			apply
+		}
+	}
+
+	/// Controller.
+	pub fn controller(&self) -> &C {
+		&self.controller
+	}
+
+	/// Controller.
+	pub fn controller_mut(&mut self) -> &mut C {
+		&mut self.controller
+	}
+}
+
+impl<T, C: Controller, F: FnMut(&mut T, &Mat4)> cycles::Worker<T> for Worker<C, F> {
+	fn cycle(&mut self, _: &T) {
+		let now = Instant::now();
+		let dt = match self.last_update.replace(now) {
+			Some(last_update) => (now - last_update).as_secs_f32(),
+			None => 0.0
+		};
+
+		let mut input = self.input.lock();
+		self.controller.update(&input, dt);
+		input.end_frame();
+
+		self.transformation = self.controller.transformation()
+	}
+
+	fn apply(&mut self, state: &mut T) {
+		(self.apply)(state, &self.transformation)
+	}
+}
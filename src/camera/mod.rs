@@ -0,0 +1,32 @@
+//! Cameras.
+use glam::{
+	Vec3,
+	Mat4
+};
+
+mod satellite;
+pub mod projection;
+pub mod controller;
+
+pub use satellite::Satellite;
+pub use controller::{
+	Controller,
+	Fly,
+	FirstPerson
+};
+pub use projection::{
+	Projection,
+	Perspective,
+	Orthographic
+};
+
+/// Camera placement.
+pub trait Camera {
+	/// View matrix, transforming world coordinates into camera coordinates.
+	fn view(&self) -> Mat4;
+
+	/// Camera position in world space.
+	fn position(&self) -> Vec3 {
+		self.view().inverse().w_axis.truncate()
+	}
+}
@@ -0,0 +1,66 @@
+use glam::Mat4;
+
+mod perspective;
+mod orthographic;
+
+pub use perspective::Perspective;
+pub use orthographic::Orthographic;
+
+/// Camera projection.
+///
+/// Projections map the camera space (looking toward `-z`) to the Vulkan clip space,
+/// where depth ranges from `0` (near plane) to `1` (far plane),
+/// or from `1` to `0` with reversed-Z.
+pub trait Projection {
+	/// The projection matrix.
+	fn matrix(&self) -> &Mat4;
+
+	/// Checks if the depth is reversed (`1` on the near plane, `0` on the far plane).
+	fn is_reversed_z(&self) -> bool;
+
+	/// Change the aspect ratio (width divided by height) of the projection.
+	fn set_aspect_ratio(&mut self, aspect: f32);
+
+	/// Adapt the projection to a render target of the given size, in pixels.
+	fn resize(&mut self, width: u32, height: u32) {
+		if width > 0 && height > 0 {
+			self.set_aspect_ratio(width as f32 / height as f32)
+		}
+	}
+}
+
+/// Axis whose extent is preserved when the aspect ratio changes.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Axis {
+	Horizontal,
+	Vertical
+}
+
+/// Extents of the view volume, on the near plane for perspective projections.
+#[derive(Clone, Copy, PartialEq, Debug)]
+struct Extents {
+	left: f32,
+	right: f32,
+	bottom: f32,
+	top: f32
+}
+
+impl Extents {
+	/// Adapt the extents to the given aspect ratio, preserving the given axis.
+	fn set_aspect_ratio(&mut self, aspect: f32, fixed: Axis) {
+		match fixed {
+			Axis::Horizontal => {
+				let center = (self.top + self.bottom) * 0.5;
+				let half_height = (self.right - self.left) * 0.5 / aspect;
+				self.bottom = center - half_height;
+				self.top = center + half_height
+			},
+			Axis::Vertical => {
+				let center = (self.right + self.left) * 0.5;
+				let half_width = (self.top - self.bottom) * 0.5 * aspect;
+				self.left = center - half_width;
+				self.right = center + half_width
+			}
+		}
+	}
+}
@@ -0,0 +1,97 @@
+use glam::{
+	Vec4,
+	Mat4
+};
+use super::{
+	Projection,
+	Axis,
+	Extents
+};
+
+/// Orthographic projection.
+///
+/// Orthographic projections always have a finite far plane.
+pub struct Orthographic {
+	extents: Extents,
+	near: f32,
+	far: f32,
+	reversed_z: bool,
+	matrix: Mat4
+}
+
+impl Orthographic {
+	/// Create a new orthographic projection.
+	///
+	/// When the aspect ratio changes, the vertical extent is preserved.
+	pub fn new(left: f32, right: f32, bottom: f32, top: f32, near: f32, far: f32) -> Orthographic {
+		let mut p = Orthographic {
+			extents: Extents { left, right, bottom, top },
+			near,
+			far,
+			reversed_z: false,
+			matrix: Mat4::identity()
+		};
+
+		p.update();
+		p
+	}
+
+	/// Create a new orthographic projection centered on the view axis,
+	/// with the given height and aspect ratio.
+	pub fn from_height(height: f32, aspect: f32, near: f32, far: f32) -> Orthographic {
+		let top = height * 0.5;
+		let right = top * aspect;
+		Self::new(-right, right, -top, top, near, far)
+	}
+
+	/// Enable or disable reversed-Z.
+	pub fn with_reversed_z(mut self, reversed_z: bool) -> Self {
+		self.reversed_z = reversed_z;
+		self.update();
+		self
+	}
+
+	/// Distance to the near plane.
+	pub fn near(&self) -> f32 {
+		self.near
+	}
+
+	/// Distance to the far plane.
+	pub fn far(&self) -> f32 {
+		self.far
+	}
+
+	fn update(&mut self) {
+		let Extents { left: l, right: r, bottom: b, top: t } = self.extents;
+		let (n, f) = (self.near, self.far);
+
+		// Depth coefficients, such that `z_clip = a * z + b`.
+		let (a, b) = if self.reversed_z {
+			(1.0 / (f - n), f / (f - n))
+		} else {
+			(-1.0 / (f - n), -n / (f - n))
+		};
+
+		self.matrix = Mat4::from_cols(
+			Vec4::new(2.0 / (r - l), 0.0, 0.0, 0.0),
+			Vec4::new(0.0, 2.0 / (t - b), 0.0, 0.0),
+			Vec4::new(0.0, 0.0, a, 0.0),
+			Vec4::new(-(r + l) / (r - l), -(t + b) / (t - b), b, 1.0)
+		)
+	}
+}
+
+impl Projection for Orthographic {
+	fn matrix(&self) -> &Mat4 {
+		&self.matrix
+	}
+
+	fn is_reversed_z(&self) -> bool {
+		self.reversed_z
+	}
+
+	fn set_aspect_ratio(&mut self, aspect: f32) {
+		self.extents.set_aspect_ratio(aspect, Axis::Vertical);
+		self.update()
+	}
+}
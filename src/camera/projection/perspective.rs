@@ -0,0 +1,139 @@
+use glam::{
+	Vec4,
+	Mat4
+};
+use super::{
+	Projection,
+	Axis,
+	Extents
+};
+
+/// Perspective projection.
+pub struct Perspective {
+	extents: Extents,
+	fixed: Axis,
+	near: f32,
+
+	/// Far plane distance, or `None` for an infinite far plane.
+	far: Option<f32>,
+
+	reversed_z: bool,
+	matrix: Mat4
+}
+
+impl Perspective {
+	/// Create a new perspective projection from the extents of the view volume on the near plane.
+	///
+	/// When the aspect ratio changes, the vertical extent is preserved.
+	pub fn new(left: f32, right: f32, bottom: f32, top: f32, near: f32, far: f32) -> Perspective {
+		Self::from_extents(Extents { left, right, bottom, top }, Axis::Vertical, near, Some(far))
+	}
+
+	/// Create a new perspective projection with the given horizontal field of view.
+	/// The aspect ratio is the ratio of x (width) to y (height).
+	///
+	/// When the aspect ratio changes, the horizontal field of view is preserved.
+	pub fn fovx(fovx: f32, aspect: f32, near: f32, far: f32) -> Perspective {
+		let right = near * (fovx * 0.5).tan();
+		let top = right / aspect;
+		Self::from_extents(Extents { left: -right, right, bottom: -top, top }, Axis::Horizontal, near, Some(far))
+	}
+
+	/// Create a new perspective projection with the given vertical field of view.
+	/// The aspect ratio is the ratio of x (width) to y (height).
+	///
+	/// When the aspect ratio changes, the vertical field of view is preserved.
+	pub fn fovy(fovy: f32, aspect: f32, near: f32, far: f32) -> Perspective {
+		let top = near * (fovy * 0.5).tan();
+		let right = top * aspect;
+		Self::from_extents(Extents { left: -right, right, bottom: -top, top }, Axis::Vertical, near, Some(far))
+	}
+
+	fn from_extents(extents: Extents, fixed: Axis, near: f32, far: Option<f32>) -> Perspective {
+		let mut p = Perspective {
+			extents,
+			fixed,
+			near,
+			far,
+			reversed_z: false,
+			matrix: Mat4::identity()
+		};
+
+		p.update();
+		p
+	}
+
+	/// Enable or disable reversed-Z.
+	pub fn with_reversed_z(mut self, reversed_z: bool) -> Self {
+		self.reversed_z = reversed_z;
+		self.update();
+		self
+	}
+
+	/// Push the far plane to infinity.
+	pub fn with_infinite_far(mut self) -> Self {
+		self.far = None;
+		self.update();
+		self
+	}
+
+	/// Distance to the near plane.
+	pub fn near(&self) -> f32 {
+		self.near
+	}
+
+	/// Distance to the far plane, or `None` if it is infinite.
+	pub fn far(&self) -> Option<f32> {
+		self.far
+	}
+
+	/// Horizontal field of view.
+	pub fn horizontal_fov(&self) -> f32 {
+		(self.extents.right / self.near).atan() - (self.extents.left / self.near).atan()
+	}
+
+	/// Vertical field of view.
+	pub fn vertical_fov(&self) -> f32 {
+		(self.extents.top / self.near).atan() - (self.extents.bottom / self.near).atan()
+	}
+
+	/// Aspect ratio (width divided by height).
+	pub fn aspect_ratio(&self) -> f32 {
+		(self.extents.right - self.extents.left) / (self.extents.top - self.extents.bottom)
+	}
+
+	fn update(&mut self) {
+		let Extents { left: l, right: r, bottom: b, top: t } = self.extents;
+		let n = self.near;
+
+		// Depth coefficients, such that `z_clip = a * z + b` and `w_clip = -z`.
+		let (a, b) = match (self.far, self.reversed_z) {
+			(Some(f), false) => (-f / (f - n), -f * n / (f - n)),
+			(Some(f), true) => (n / (f - n), f * n / (f - n)),
+			(None, false) => (-1.0, -n),
+			(None, true) => (0.0, n)
+		};
+
+		self.matrix = Mat4::from_cols(
+			Vec4::new(2.0 * n / (r - l), 0.0, 0.0, 0.0),
+			Vec4::new(0.0, 2.0 * n / (t - b), 0.0, 0.0),
+			Vec4::new((r + l) / (r - l), (t + b) / (t - b), a, -1.0),
+			Vec4::new(0.0, 0.0, b, 0.0)
+		)
+	}
+}
+
+impl Projection for Perspective {
+	fn matrix(&self) -> &Mat4 {
+		&self.matrix
+	}
+
+	fn is_reversed_z(&self) -> bool {
+		self.reversed_z
+	}
+
+	fn set_aspect_ratio(&mut self, aspect: f32) {
+		self.extents.set_aspect_ratio(aspect, self.fixed);
+		self.update()
+	}
+}
@@ -0,0 +1,219 @@
+use std::f32::consts::PI;
+use glam::{
+	Vec2,
+	Vec3,
+	Mat4
+};
+use super::Camera;
+
+/// Minimum distance between the polar angle and the poles.
+const POLE_MARGIN: f32 = 1e-3;
+
+/// Orbit camera.
+///
+/// The camera moves on a sphere around a center point, always looking at it.
+/// Its position on the sphere is given by its surface coordinates:
+/// the polar angle, measured from the up (`z`) axis,
+/// and the azimuthal angle, measured around the up axis from the `x` axis.
+///
+/// Changes are applied smoothly over time if damping is enabled,
+/// by calling [`Satellite::update`] each frame.
+pub struct Satellite {
+	center: Vec3,
+
+	/// Current distance and surface coordinates.
+	distance: f32,
+	polar: f32,
+	azimuthal: f32,
+
+	/// Distance and surface coordinates to reach.
+	target_distance: f32,
+	target_polar: f32,
+	target_azimuthal: f32,
+
+	/// Distance range.
+	min_distance: f32,
+	max_distance: f32,
+
+	/// Polar angle range.
+	min_polar: f32,
+	max_polar: f32,
+
+	/// Time (in seconds) needed to cover ~63% of the remaining movement.
+	damping: f32,
+
+	/// Rotation angle (in radians) per dragged pixel.
+	sensitivity: f32
+}
+
+impl Satellite {
+	/// Create a new satellite camera.
+	///
+	/// By default, there is no damping and a drag of one pixel rotates the camera by `0.01` radians.
+	pub fn new(center: Vec3, distance: f32, polar: f32, azimuthal: f32) -> Self {
+		let min_polar = POLE_MARGIN;
+		let max_polar = PI - POLE_MARGIN;
+		let polar = polar.max(min_polar).min(max_polar);
+
+		Self {
+			center,
+			distance,
+			polar,
+			azimuthal,
+			target_distance: distance,
+			target_polar: polar,
+			target_azimuthal: azimuthal,
+			min_distance: 0.0,
+			max_distance: std::f32::INFINITY,
+			min_polar,
+			max_polar,
+			damping: 0.0,
+			sensitivity: 0.01
+		}
+	}
+
+	/// Point the camera orbits around.
+	pub fn center(&self) -> Vec3 {
+		self.center
+	}
+
+	/// Change the point the camera orbits around.
+	pub fn set_center(&mut self, center: Vec3) {
+		self.center = center
+	}
+
+	/// Current distance to the center.
+	pub fn distance(&self) -> f32 {
+		self.distance
+	}
+
+	/// Current polar angle.
+	pub fn polar(&self) -> f32 {
+		self.polar
+	}
+
+	/// Current azimuthal angle.
+	pub fn azimuthal(&self) -> f32 {
+		self.azimuthal
+	}
+
+	/// Restrict the distance to the center to the given range.
+	///
+	/// The current and target distances are clamped to the new range.
+	pub fn set_distance_range(&mut self, min: f32, max: f32) {
+		self.min_distance = min;
+		self.max_distance = max;
+		self.distance = self.distance.max(min).min(max);
+		self.target_distance = self.target_distance.max(min).min(max)
+	}
+
+	/// Restrict the polar angle to the given range.
+	///
+	/// The range is always kept away from the poles, where the camera orientation is undefined.
+	/// The current and target polar angles are clamped to the new range.
+	pub fn set_polar_range(&mut self, min: f32, max: f32) {
+		self.min_polar = min.max(POLE_MARGIN);
+		self.max_polar = max.min(PI - POLE_MARGIN);
+		self.polar = self.polar.max(self.min_polar).min(self.max_polar);
+		self.target_polar = self.target_polar.max(self.min_polar).min(self.max_polar)
+	}
+
+	/// Damping time, in seconds.
+	///
+	/// A value of `0.0` disables damping.
+	pub fn set_damping(&mut self, damping: f32) {
+		self.damping = damping.max(0.0)
+	}
+
+	/// Rotation angle (in radians) per dragged pixel.
+	pub fn sensitivity(&self) -> f32 {
+		self.sensitivity
+	}
+
+	/// Change the rotation angle (in radians) per dragged pixel.
+	pub fn set_sensitivity(&mut self, sensitivity: f32) {
+		self.sensitivity = sensitivity
+	}
+
+	/// Set the sensitivity so that dragging across the whole viewport height
+	/// moves the camera from one pole to the other.
+	pub fn fit_sensitivity(&mut self, viewport_height: f32) {
+		self.sensitivity = PI / viewport_height
+	}
+
+	/// Angle deltas `(polar, azimuthal)` corresponding to the given drag, in pixels.
+	///
+	/// Dragging to the right turns the camera to the left around the center,
+	/// dragging down moves the camera up.
+	pub fn drag_to_angles(&self, drag: Vec2) -> (f32, f32) {
+		(-drag.y * self.sensitivity, -drag.x * self.sensitivity)
+	}
+
+	/// Drag, in pixels, corresponding to the given angle deltas.
+	pub fn angles_to_drag(&self, delta_polar: f32, delta_azimuthal: f32) -> Vec2 {
+		Vec2::new(-delta_azimuthal, -delta_polar) / self.sensitivity
+	}
+
+	/// Move the camera on its sphere.
+	pub fn move_by(&mut self, delta_polar: f32, delta_azimuthal: f32) {
+		self.target_polar = (self.target_polar + delta_polar).max(self.min_polar).min(self.max_polar);
+		self.target_azimuthal += delta_azimuthal;
+
+		if self.damping == 0.0 {
+			self.snap()
+		}
+	}
+
+	/// Move the camera according to the given drag, in pixels.
+	pub fn drag(&mut self, drag: Vec2) {
+		let (delta_polar, delta_azimuthal) = self.drag_to_angles(drag);
+		self.move_by(delta_polar, delta_azimuthal)
+	}
+
+	/// Multiply the distance to the center by the given factor.
+	///
+	/// A factor lower than `1` brings the camera closer.
+	pub fn zoom(&mut self, factor: f32) {
+		self.target_distance = (self.target_distance * factor).max(self.min_distance).min(self.max_distance);
+
+		if self.damping == 0.0 {
+			self.snap()
+		}
+	}
+
+	/// Immediately reach the target position.
+	pub fn snap(&mut self) {
+		self.distance = self.target_distance;
+		self.polar = self.target_polar;
+		self.azimuthal = self.target_azimuthal
+	}
+
+	/// Move toward the target position, given the time elapsed (in seconds) since the last update.
+	pub fn update(&mut self, dt: f32) {
+		if self.damping == 0.0 {
+			self.snap()
+		} else {
+			let t = 1.0 - (-dt / self.damping).exp();
+			self.distance += (self.target_distance - self.distance) * t;
+			self.polar += (self.target_polar - self.polar) * t;
+			self.azimuthal += (self.target_azimuthal - self.azimuthal) * t
+		}
+	}
+
+	/// Camera position relative to the center.
+	fn offset(&self) -> Vec3 {
+		let (sin_polar, cos_polar) = (self.polar.sin(), self.polar.cos());
+		let (sin_azimuthal, cos_azimuthal) = (self.azimuthal.sin(), self.azimuthal.cos());
+		Vec3::new(sin_polar * cos_azimuthal, sin_polar * sin_azimuthal, cos_polar) * self.distance
+	}
+}
+
+impl Camera for Satellite {
+	fn view(&self) -> Mat4 {
+		Mat4::look_at_rh(self.position(), self.center, Vec3::unit_z())
+	}
+
+	fn position(&self) -> Vec3 {
+		self.center + self.offset()
+	}
+}
@@ -0,0 +1,268 @@
+//! Compute jobs.
+//!
+//! Compute jobs are dispatched by a dedicated [`Thread`] on a compute queue.
+//! Their completion is tracked by the loader [`Worker`](crate::sync::loader::Worker),
+//! so that their results are resolved during its apply phase, like loaded buffers.
+use std::sync::Arc;
+use crossbeam_channel::Sender;
+use crossbeam_queue::SegQueue;
+use magma::{
+	Device,
+	DeviceOwned,
+	descriptor,
+	pipeline::{
+		self,
+		shader
+	},
+	mem::Allocator
+};
+use crate::sync::loader::{
+	self,
+	Loader,
+	Loading,
+	loading
+};
+
+mod thread;
+
+pub use thread::Thread;
+
+/// Compute shader.
+pub struct ComputeShader(Arc<shader::Module>);
+
+impl ComputeShader {
+	/// Create a compute shader from a compiled SPIR-V module, with a `main` entry point.
+	pub fn new(module: Arc<shader::Module>) -> ComputeShader {
+		ComputeShader(module)
+	}
+
+	pub fn entry_point(&self) -> shader::EntryPoint {
+		unsafe {
+			self.0.entry_point("main")
+		}
+	}
+}
+
+/// Compute pipeline.
+///
+/// Storage buffers are bound to the set `0`, at bindings `0..storage_buffers`.
+/// Push constants are available to the shader from offset `0`.
+pub struct Pipeline {
+	handle: pipeline::Compute,
+	layout: Arc<pipeline::Layout>,
+	set_layout: Arc<descriptor::SetLayout>,
+	storage_buffers: u32,
+	push_constants_size: u32,
+
+	/// Descriptor sets of completed jobs, reused by the next ones.
+	sets: SegQueue<descriptor::Set>
+}
+
+impl Pipeline {
+	pub fn new(device: &Arc<Device>, shader: &ComputeShader, storage_buffers: u32, push_constants_size: u32) -> Self {
+		let bindings: Vec<_> = (0..storage_buffers).map(|binding| {
+			descriptor::set_layout::Binding::new(binding, descriptor::Type::StorageBuffer, pipeline::shader::Stage::Compute)
+		}).collect();
+
+		let set_layout = Arc::new(descriptor::SetLayout::new(device, &bindings).expect("unable to create descriptor set layout"));
+
+		let push_constant_ranges: Vec<_> = if push_constants_size > 0 {
+			vec![pipeline::layout::PushConstantRange::new(pipeline::shader::Stage::Compute, 0, push_constants_size)]
+		} else {
+			Vec::new()
+		};
+
+		let layout = Arc::new(pipeline::Layout::new(
+			device,
+			&[set_layout.clone()],
+			&push_constant_ranges
+		).expect("unable to create layout"));
+
+		let handle = pipeline::Compute::new(
+			device,
+			unsafe { pipeline::stage::Compute::new(shader.entry_point()) },
+			&layout
+		).expect("unable to build compute pipeline");
+
+		Self {
+			handle,
+			layout,
+			set_layout,
+			storage_buffers,
+			push_constants_size,
+			sets: SegQueue::new()
+		}
+	}
+
+	pub fn handle(&self) -> &pipeline::Compute {
+		&self.handle
+	}
+
+	pub fn layout(&self) -> &Arc<pipeline::Layout> {
+		&self.layout
+	}
+
+	pub fn set_layout(&self) -> &Arc<descriptor::SetLayout> {
+		&self.set_layout
+	}
+
+	/// Number of storage buffers bound to the pipeline.
+	pub fn storage_buffers(&self) -> u32 {
+		self.storage_buffers
+	}
+
+	/// Descriptor set for a new job, reused from a completed job if possible.
+	pub(crate) fn descriptor_set(&self) -> descriptor::Set {
+		match self.sets.pop() {
+			Some(set) => set,
+			None => {
+				let pool = descriptor::Pool::new(
+					self.layout.device(),
+					1,
+					&[descriptor::pool::Size::new(descriptor::Type::StorageBuffer, self.storage_buffers)]
+				).expect("unable to create descriptor pool");
+
+				pool.allocate(&self.set_layout).expect("unable to allocate descriptor set")
+			}
+		}
+	}
+
+	/// Give back the descriptor set of a completed job.
+	pub(crate) fn recycle(&self, set: descriptor::Set) {
+		self.sets.push(set)
+	}
+}
+
+/// Compute job.
+pub struct Job {
+	pipeline: Arc<Pipeline>,
+	buffers: Vec<Arc<dyn Send + Sync + magma::Buffer>>,
+	push_constants: Vec<u8>,
+	groups: (u32, u32, u32)
+}
+
+impl Job {
+	/// Create a new job dispatching the given number of work groups.
+	pub fn new(pipeline: &Arc<Pipeline>, groups: (u32, u32, u32)) -> Self {
+		Self {
+			pipeline: pipeline.clone(),
+			buffers: Vec::new(),
+			push_constants: Vec::new(),
+			groups
+		}
+	}
+
+	/// Bind the given storage buffer at the next binding.
+	///
+	/// Buffers loaded with the [`Loader`](crate::sync::Loader) must have the `StorageBuffer` usage,
+	/// and be shared with the compute queue.
+	pub fn with_buffer<B: 'static + Send + Sync + magma::Buffer>(mut self, buffer: Arc<B>) -> Self {
+		self.buffers.push(buffer);
+		self
+	}
+
+	/// Set the push constants of the job.
+	pub fn with_push_constants<T: Copy>(mut self, value: &T) -> Self {
+		let len = std::mem::size_of::<T>();
+		assert!(len as u32 <= self.pipeline.push_constants_size, "push constants too large");
+
+		let bytes = unsafe {
+			std::slice::from_raw_parts(value as *const T as *const u8, len)
+		};
+
+		self.push_constants = bytes.to_vec();
+		self
+	}
+}
+
+/// Job output read back to the host.
+pub(crate) struct Output {
+	/// Index of the read buffer.
+	buffer: usize,
+
+	/// Size to read, in bytes.
+	size: u64,
+
+	/// Called with the read bytes, once the job is done.
+	handle: loading::Handle<Vec<u8>>
+}
+
+pub(crate) enum Query {
+	Dispatch {
+		job: Job,
+		output: Option<Output>,
+		done: Option<loading::Handle<()>>
+	}
+}
+
+/// Compute jobs dispatcher.
+pub struct Compute {
+	channel: Sender<Query>
+}
+
+impl Compute {
+	/// Create a new dispatcher, and the thread executing the jobs on the given compute queue.
+	///
+	/// Jobs are completed by the given loader worker.
+	/// Buffers loaded by the given loader for the compute queue family only are acquired by the thread.
+	/// The allocator must provide host visible memory for outputs.
+	pub fn new<A: Allocator>(allocator: A, compute_queue: magma::device::Queue, loader: &Loader, worker: &loader::Worker) -> (Self, Thread<A>) {
+		let (sender, receiver) = crossbeam_channel::unbounded();
+		let thread = Thread::new(allocator, compute_queue, receiver, loader.acquisitions(), worker.pending_futures());
+
+		let compute = Self {
+			channel: sender
+		};
+
+		(compute, thread)
+	}
+
+	/// Dispatch the given job.
+	///
+	/// The returned loading is resolved once the job is done.
+	pub fn dispatch(&self, job: Job) -> Loading<()> {
+		let (loading, handle) = Loading::new();
+		self.send(Query::Dispatch {
+			job,
+			output: None,
+			done: Some(handle)
+		});
+		loading
+	}
+
+	/// Dispatch the given job, and read back the first `len` elements of the given bound buffer.
+	///
+	/// The returned loading is resolved with the elements once the job is done.
+	pub fn dispatch_and_read<T: 'static + Copy + Send + Sync>(&self, job: Job, buffer: usize, len: usize) -> Loading<Vec<T>> {
+		assert!(buffer < job.buffers.len(), "no such buffer");
+
+		let size = (len * std::mem::size_of::<T>()) as u64;
+		assert!(size <= job.buffers[buffer].size(), "read elements out of the buffer");
+
+		let (loading, handle) = Loading::mapped(move |bytes: Vec<u8>| {
+			let mut values = Vec::with_capacity(len);
+			unsafe {
+				// Safe because the buffer holds `len` values of type `T`.
+				std::ptr::copy_nonoverlapping(bytes.as_ptr(), values.as_mut_ptr() as *mut u8, bytes.len());
+				values.set_len(len)
+			}
+			values
+		});
+
+		self.send(Query::Dispatch {
+			job,
+			output: Some(Output {
+				buffer,
+				size,
+				handle
+			}),
+			done: None
+		});
+
+		loading
+	}
+
+	fn send(&self, query: Query) {
+		self.channel.send(query).expect("unable to send compute query")
+	}
+}
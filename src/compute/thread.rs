@@ -0,0 +1,207 @@
+use std::convert::TryInto;
+use crossbeam_channel::Receiver;
+use magma::{
+	device,
+	DeviceOwned,
+	command,
+	descriptor,
+	pipeline,
+	mem::{
+		Allocator,
+		HostVisibleSlot,
+		buffer
+	}
+};
+use crate::sync::{
+	FencePool,
+	CommandBufferPool,
+	loader::{
+		self,
+		Acquisitions
+	}
+};
+use super::{
+	Query,
+	Job,
+	Output
+};
+
+/// Mapped memory of a readback buffer.
+struct ReadbackPtr(*mut u8);
+
+// Safe because the memory is only read once the job is done, by the thread completing it.
+unsafe impl Send for ReadbackPtr {}
+
+/// Compute thread.
+///
+/// The compute thread is in charge of recording and submitting the dispatched jobs.
+pub struct Thread<A: Allocator> {
+	allocator: A,
+	compute_queue: device::Queue,
+	queries: Receiver<Query>,
+	acquisitions: Acquisitions,
+	worker_futures: loader::Futures,
+	fence_pool: FencePool,
+	command_buffer_pool: CommandBufferPool
+}
+
+impl<A: Allocator> Thread<A> {
+	pub(crate) fn new(
+		allocator: A,
+		compute_queue: device::Queue,
+		queries: Receiver<Query>,
+		acquisitions: &Acquisitions,
+		worker_futures: &loader::Futures
+	) -> Self {
+		let fence_pool = FencePool::new(compute_queue.device());
+		let command_buffer_pool = CommandBufferPool::new(&compute_queue).expect("unable to create command buffer pool");
+
+		Self {
+			allocator,
+			compute_queue,
+			queries,
+			acquisitions: acquisitions.clone(),
+			worker_futures: worker_futures.clone(),
+			fence_pool,
+			command_buffer_pool
+		}
+	}
+
+	/// Bind the job buffers to a descriptor set of the job pipeline.
+	///
+	/// The set must be given back to the pipeline once the job is done.
+	fn descriptor_set(&self, job: &Job) -> descriptor::Set {
+		let pipeline = &job.pipeline;
+		assert_eq!(job.buffers.len() as u32, pipeline.storage_buffers(), "wrong number of storage buffers");
+
+		let mut set = pipeline.descriptor_set();
+		for (binding, buffer) in job.buffers.iter().enumerate() {
+			set.write_storage_buffer(binding as u32, buffer.clone())
+		}
+
+		set
+	}
+
+	/// Create the host visible buffer receiving the job output.
+	fn readback_buffer(&mut self, size: u64) -> (buffer::Bound, ReadbackPtr) {
+		let buffer = buffer::Unbound::new(
+			self.compute_queue.device(),
+			size,
+			buffer::Usage::TransferDestination,
+			&self.compute_queue
+		).expect("unable to create readback buffer");
+
+		let slot: A::HostVisibleSlot = self.allocator.allocate(buffer.memory_requirements()).try_into().ok().expect("memory is not host visible");
+		let ptr = slot.ptr().expect("unable to map readback buffer memory") as *mut u8;
+		match unsafe { buffer.bind(slot) } {
+			Ok(bound) => (bound, ReadbackPtr(ptr)),
+			Err((_, e)) => panic!("unable to bind readback buffer memory: {:?}", e)
+		}
+	}
+
+	fn dispatch(&mut self, job: Job, output: Option<Output>, done: Option<loader::loading::Handle<()>>) {
+		let set = self.descriptor_set(&job);
+		let readback = output.map(|output| {
+			let (buffer, ptr) = self.readback_buffer(output.size);
+			(output, buffer, ptr)
+		});
+
+		let command_buffer = self.command_buffer_pool.get().expect("unable to allocate command buffer");
+		let recorded_command_buffer = command_buffer.record(|commands| {
+			self.acquisitions.record(commands, &self.compute_queue);
+
+			let pipeline = &job.pipeline;
+			commands.bind_compute_pipeline(pipeline.handle());
+			commands.bind_descriptor_sets(pipeline::BindPoint::Compute, pipeline.layout(), 0, &[&set]);
+
+			if !job.push_constants.is_empty() {
+				commands.push_constants(pipeline.layout(), pipeline::shader::Stage::Compute, 0, &job.push_constants);
+			}
+
+			let (x, y, z) = job.groups;
+			commands.dispatch(x, y, z);
+
+			if let Some((output, readback_buffer, _)) = &readback {
+				let src = job.buffers[output.buffer].clone();
+
+				commands.pipeline_barrier(
+					pipeline::Stage::ComputeShader,
+					pipeline::Stage::Transfer,
+					&[command::buffer::BufferMemoryBarrier {
+						buffer: src.handle(),
+						offset: 0,
+						size: output.size,
+						src_access: command::buffer::Access::ShaderWrite,
+						dst_access: command::buffer::Access::TransferRead,
+						src_queue_family: command::buffer::QUEUE_FAMILY_IGNORED,
+						dst_queue_family: command::buffer::QUEUE_FAMILY_IGNORED
+					}]
+				);
+
+				commands.copy_buffer(src, readback_buffer, &[command::buffer::BufferCopy {
+					src_offset: 0,
+					dst_offset: 0,
+					size: output.size
+				}]);
+
+				commands.pipeline_barrier(
+					pipeline::Stage::Transfer,
+					pipeline::Stage::Host,
+					&[command::buffer::BufferMemoryBarrier {
+						buffer: readback_buffer.handle(),
+						offset: 0,
+						size: output.size,
+						src_access: command::buffer::Access::TransferWrite,
+						dst_access: command::buffer::Access::HostRead,
+						src_queue_family: command::buffer::QUEUE_FAMILY_IGNORED,
+						dst_queue_family: command::buffer::QUEUE_FAMILY_IGNORED
+					}]
+				);
+			}
+		}).expect("unable to record command buffer");
+
+		let fence = self.fence_pool.get().expect("unable to create fence");
+		let (_, future) = self.compute_queue.submit(recorded_command_buffer).then_signal_fence(fence).expect("unable to submit command buffer");
+
+		// The job resources are kept alive until its completion.
+		let future = loader::Future::new(future).on_complete(move || {
+			job.pipeline.recycle(set);
+
+			if let Some((output, readback_buffer, ptr)) = readback {
+				readback_buffer.invalidate_mapped_memory().expect("unable to invalidate readback memory");
+
+				let mut bytes = vec![0u8; output.size as usize];
+				unsafe {
+					// Safe because the job has been executed.
+					std::ptr::copy_nonoverlapping(ptr.0 as *const u8, bytes.as_mut_ptr(), bytes.len())
+				}
+
+				std::mem::drop(readback_buffer);
+				output.handle.prepare(bytes);
+			}
+
+			if let Some(done) = done {
+				done.prepare(());
+			}
+
+			std::mem::drop(job)
+		});
+
+		self.worker_futures.push(future)
+	}
+
+	/// Run the compute thread, until every dispatcher has been dropped.
+	pub fn run(&mut self) {
+		loop {
+			match self.queries.recv() {
+				Ok(Query::Dispatch { job, output, done }) => {
+					self.dispatch(job, output, done)
+				},
+				Err(e) => {
+					log::error!("compute Thread error: {}", e);
+					break
+				}
+			}
+		}
+	}
+}
@@ -0,0 +1,212 @@
+//! Backend-neutral input model.
+//!
+//! Windowing backends translate their own events into [`Event`]s,
+//! that are accumulated into an [`InputState`] until the end of the frame.
+use std::{
+	sync::Arc,
+	collections::{
+		HashSet,
+		HashMap
+	}
+};
+use glam::Vec2;
+use parking_lot::{
+	Mutex,
+	MutexGuard
+};
+
+/// Keyboard key.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Key {
+	A, B, C, D, E, F, G, H, I, J, K, L, M,
+	N, O, P, Q, R, S, T, U, V, W, X, Y, Z,
+	Digit0, Digit1, Digit2, Digit3, Digit4,
+	Digit5, Digit6, Digit7, Digit8, Digit9,
+	Up,
+	Down,
+	Left,
+	Right,
+	Space,
+	Enter,
+	Escape,
+	Tab,
+	Backspace,
+	Shift,
+	Control,
+	Alt,
+
+	/// Any other key, identified by a backend-specific code.
+	Other(u32)
+}
+
+/// Mouse button.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum MouseButton {
+	Left,
+	Right,
+	Middle,
+
+	/// Any other button, identified by a backend-specific code.
+	Other(u16)
+}
+
+/// Touch point.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Touch {
+	/// Touch identifier, unique while the touch lasts.
+	pub id: u64,
+
+	/// Position, in pixels.
+	pub position: Vec2,
+
+	/// Movement since the beginning of the frame, in pixels.
+	pub delta: Vec2
+}
+
+/// Input event.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Event {
+	KeyPressed(Key),
+	KeyReleased(Key),
+	ButtonPressed(MouseButton),
+	ButtonReleased(MouseButton),
+
+	/// Raw mouse movement, in pixels (or backend-specific units).
+	MouseMoved(Vec2),
+
+	/// The cursor moved to the given position, in pixels.
+	CursorMoved(Vec2),
+
+	/// The cursor left the window.
+	CursorLeft,
+
+	/// Scroll, in lines.
+	Scrolled(Vec2),
+
+	TouchStarted(u64, Vec2),
+	TouchMoved(u64, Vec2),
+	TouchEnded(u64),
+
+	/// The window lost the focus.
+	///
+	/// Every key and button is released.
+	FocusLost
+}
+
+/// Input state.
+#[derive(Default)]
+pub struct InputState {
+	keys: HashSet<Key>,
+	buttons: HashSet<MouseButton>,
+	cursor: Option<Vec2>,
+	mouse_delta: Vec2,
+	scroll: Vec2,
+	touches: HashMap<u64, Touch>
+}
+
+impl InputState {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Update the state with the given event.
+	pub fn handle(&mut self, event: Event) {
+		match event {
+			Event::KeyPressed(key) => {
+				self.keys.insert(key);
+			},
+			Event::KeyReleased(key) => {
+				self.keys.remove(&key);
+			},
+			Event::ButtonPressed(button) => {
+				self.buttons.insert(button);
+			},
+			Event::ButtonReleased(button) => {
+				self.buttons.remove(&button);
+			},
+			Event::MouseMoved(delta) => self.mouse_delta += delta,
+			Event::CursorMoved(position) => self.cursor = Some(position),
+			Event::CursorLeft => self.cursor = None,
+			Event::Scrolled(delta) => self.scroll += delta,
+			Event::TouchStarted(id, position) => {
+				self.touches.insert(id, Touch { id, position, delta: Vec2::zero() });
+			},
+			Event::TouchMoved(id, position) => {
+				if let Some(touch) = self.touches.get_mut(&id) {
+					touch.delta += position - touch.position;
+					touch.position = position
+				}
+			},
+			Event::TouchEnded(id) => {
+				self.touches.remove(&id);
+			},
+			Event::FocusLost => {
+				self.keys.clear();
+				self.buttons.clear();
+				self.touches.clear()
+			}
+		}
+	}
+
+	/// Checks if the given key is currently pressed.
+	pub fn is_key_pressed(&self, key: Key) -> bool {
+		self.keys.contains(&key)
+	}
+
+	/// Checks if the given mouse button is currently pressed.
+	pub fn is_button_pressed(&self, button: MouseButton) -> bool {
+		self.buttons.contains(&button)
+	}
+
+	/// Cursor position, in pixels, if it is in the window.
+	pub fn cursor(&self) -> Option<Vec2> {
+		self.cursor
+	}
+
+	/// Mouse movement since the beginning of the frame.
+	pub fn mouse_delta(&self) -> Vec2 {
+		self.mouse_delta
+	}
+
+	/// Scroll since the beginning of the frame.
+	pub fn scroll(&self) -> Vec2 {
+		self.scroll
+	}
+
+	/// Current touch points.
+	pub fn touches(&self) -> impl Iterator<Item=&Touch> {
+		self.touches.values()
+	}
+
+	/// Reset the per-frame movements (mouse, scroll and touches deltas).
+	pub fn end_frame(&mut self) {
+		self.mouse_delta = Vec2::zero();
+		self.scroll = Vec2::zero();
+		for touch in self.touches.values_mut() {
+			touch.delta = Vec2::zero()
+		}
+	}
+}
+
+/// Shared input state.
+///
+/// The event loop feeds events through [`Input::handle`],
+/// while workers read the state with [`Input::lock`].
+#[derive(Clone, Default)]
+pub struct Input(Arc<Mutex<InputState>>);
+
+impl Input {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Update the state with the given event.
+	pub fn handle(&self, event: Event) {
+		self.0.lock().handle(event)
+	}
+
+	/// Lock the input state.
+	pub fn lock(&self) -> MutexGuard<InputState> {
+		self.0.lock()
+	}
+}
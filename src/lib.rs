@@ -2,10 +2,17 @@
 #![feature(drain_filter)]
 
 pub mod util;
+pub mod bounds;
+pub mod space;
+pub mod camera;
+pub mod animation;
+pub mod input;
+pub mod picking;
 pub mod sync;
 pub mod view;
 pub mod render;
-// pub mod waiter;
+pub mod compute;
+pub mod waiter;
 
 pub use view::View;
-// pub use waiter::Waiter;
\ No newline at end of file
+pub use waiter::Waiter;
\ No newline at end of file
@@ -0,0 +1,155 @@
+//! Object picking.
+use std::hash::Hash;
+use glam::{
+	Vec2,
+	Vec3,
+	Vec4,
+	Mat4
+};
+use scene::Id;
+use crate::{
+	bounds::Ray,
+	view::{
+		Object,
+		object::Primitive,
+		geometry::projection::CameraProjection
+	},
+	space::Index
+};
+
+/// Tolerance used to detect points at infinity.
+const EPSILON: f32 = 1e-6;
+
+/// Picking result.
+pub struct Hit<T> {
+	/// Picked object.
+	pub object: Id<T>,
+
+	/// Hit position, in world space.
+	pub position: Vec3,
+
+	/// Index of the hit triangle in the object geometry.
+	///
+	/// For triangle strips, this is the index of the first vertex of the triangle in the strip.
+	pub triangle: usize,
+
+	/// Distance between the ray origin and the hit position.
+	pub distance: f32
+}
+
+/// Ray, in world space, going through the given screen point.
+///
+/// The screen point is given in pixels from the top-left corner of the viewport.
+/// The ray starts on the camera near plane.
+/// Works with both reversed and standard depth, and infinite far planes.
+pub fn unproject(camera: &CameraProjection, point: Vec2, viewport: Vec2) -> Ray {
+	let ndc = point / viewport * 2.0 - Vec2::one();
+	let inverse_projection = camera.proj.inverse();
+	let a = inverse_projection * Vec4::new(ndc.x, ndc.y, 0.0, 1.0);
+	let b = inverse_projection * Vec4::new(ndc.x, ndc.y, 1.0, 1.0);
+
+	// Find the near point. The far point may be at infinity (`w = 0`).
+	let (near, far) = if a.w.abs() < EPSILON {
+		(b, a)
+	} else if b.w.abs() < EPSILON || a.z / a.w > b.z / b.w {
+		(a, b)
+	} else {
+		(b, a)
+	};
+
+	let origin = near.truncate() / near.w;
+	let direction = if far.w.abs() < EPSILON {
+		// Direction toward the point at infinity, in front of the camera.
+		let d = far.truncate();
+		if d.z > 0.0 { -d } else { d }
+	} else {
+		far.truncate() / far.w - origin
+	};
+
+	let inverse_view = camera.modelview.inverse();
+	Ray::new(inverse_view.transform_point3(origin), inverse_view.transform_vector3(direction))
+}
+
+/// Find the object nearest to the ray origin hit by the ray.
+///
+/// Each candidate is given with its transformation (object to world space) and drawn object.
+/// The bounding box of each candidate is tested first,
+/// then the triangles of its geometry, at the precision used to draw it (`0`).
+/// Objects drawn with lines or points cannot be hit, and are skipped.
+pub fn pick_ray<'a, T: 'a, I>(ray: &Ray, candidates: I) -> Option<Hit<T>> where Id<T>: Copy, I: IntoIterator<Item=(Id<T>, Mat4, &'a Object)> {
+	let mut nearest: Option<Hit<T>> = None;
+
+	for (object, transformation, view) in candidates {
+		let geometry = view.geometry();
+		let triangles: Box<dyn '_ + Iterator<Item=[Vec3; 3]>> = match view.primitive() {
+			Primitive::Triangles => Box::new(geometry.triangles(0)),
+			Primitive::TriangleStrip => Box::new(geometry.strip_triangles(0)),
+			_ => continue
+		};
+
+		let local_ray = ray.transformed(&transformation.inverse());
+
+		// Convert a local distance along the local ray into a world hit.
+		let to_world = |local_distance: f32| {
+			let position = transformation.transform_point3(local_ray.at(local_distance));
+			(position, (position - ray.origin).length())
+		};
+
+		// Bounds test.
+		let bounds = match geometry.bounds() {
+			Some(bounds) => bounds,
+			None => continue
+		};
+
+		match local_ray.intersect_aabb(bounds) {
+			Some(t) => {
+				let (_, distance) = to_world(t);
+				if nearest.as_ref().map(|hit| distance >= hit.distance).unwrap_or(false) {
+					continue
+				}
+			},
+			None => continue
+		}
+
+		// Triangles test.
+		for (triangle, [a, b, c]) in triangles.enumerate() {
+			if let Some((t, _, _)) = local_ray.intersect_triangle(a, b, c) {
+				let (position, distance) = to_world(t);
+				if nearest.as_ref().map(|hit| distance < hit.distance).unwrap_or(true) {
+					nearest = Some(Hit {
+						object,
+						position,
+						triangle,
+						distance
+					})
+				}
+			}
+		}
+	}
+
+	nearest
+}
+
+/// Find the object under the given screen point.
+///
+/// See [`unproject`] and [`pick_ray`].
+pub fn pick<'a, T: 'a, I>(camera: &CameraProjection, point: Vec2, viewport: Vec2, candidates: I) -> Option<Hit<T>> where Id<T>: Copy, I: IntoIterator<Item=(Id<T>, Mat4, &'a Object)> {
+	pick_ray(&unproject(camera, point, viewport), candidates)
+}
+
+/// Find the object nearest to the ray origin hit by the ray,
+/// among the objects of the given spatial index.
+///
+/// Only the objects whose world bounding box is hit are considered.
+/// The `object` function gives the transformation (object to world space) and drawn object of each object.
+pub fn pick_ray_in<'a, T: 'a, F>(index: &Index<T>, ray: &Ray, max_distance: f32, mut object: F) -> Option<Hit<T>> where Id<T>: Copy + Eq + Hash, F: FnMut(Id<T>) -> Option<(Mat4, &'a Object)> {
+	let mut candidates = Vec::new();
+	index.query_ray(ray, max_distance, |id, distance| candidates.push((id, distance)));
+
+	// Test the nearest candidates first, so that farther ones are quickly discarded.
+	candidates.sort_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+	pick_ray(ray, candidates.into_iter().filter_map(|(id, _)| {
+		object(id).map(|(transformation, view)| (id, transformation, view))
+	}))
+}
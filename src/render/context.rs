@@ -15,4 +15,9 @@ pub trait Context {
 
 	/// Resources loader.
 	fn loader(&self) -> &Loader;
+
+	/// Call the given function once the commands recorded with this context have been executed.
+	///
+	/// Used to know when the GPU is done reading resources drawn in a frame.
+	fn on_frame_complete(&self, f: Box<dyn FnOnce()>);
 }
\ No newline at end of file
@@ -0,0 +1,109 @@
+use std::cmp::Ordering;
+use scene::Id;
+
+/// Sorted list of objects to draw.
+///
+/// Opaque objects are sorted by pipeline, then by geometry, then front to back,
+/// to minimize state changes and overdraw.
+/// Blended objects are drawn after every opaque object, back to front,
+/// so that transparency is correctly rendered.
+pub struct DrawList<T> {
+	opaque: Vec<Draw<T>>,
+	blended: Vec<Draw<T>>
+}
+
+/// Draw list entry.
+pub struct Draw<T> {
+	/// Object to draw.
+	pub object: Id<T>,
+
+	/// Sort key.
+	pub key: Key
+}
+
+/// Draw sort key.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Key {
+	/// Graphics pipeline identifier.
+	pub pipeline: usize,
+
+	/// Geometry buffers identifier.
+	pub geometry: usize,
+
+	/// Distance between the point of view and the object.
+	pub distance: f32
+}
+
+impl Key {
+	/// Compare by state, then front to back.
+	fn cmp_opaque(&self, other: &Self) -> Ordering {
+		self.pipeline.cmp(&other.pipeline)
+			.then(self.geometry.cmp(&other.geometry))
+			.then(cmp_distance(self.distance, other.distance))
+	}
+
+	/// Compare back to front.
+	fn cmp_blended(&self, other: &Self) -> Ordering {
+		cmp_distance(other.distance, self.distance)
+	}
+}
+
+fn cmp_distance(a: f32, b: f32) -> Ordering {
+	// NaN distances are considered greater than any other distance.
+	a.partial_cmp(&b).unwrap_or_else(|| a.is_nan().cmp(&b.is_nan()))
+}
+
+impl<T> DrawList<T> {
+	pub fn new() -> Self {
+		Self {
+			opaque: Vec::new(),
+			blended: Vec::new()
+		}
+	}
+
+	/// Number of objects in the list.
+	pub fn len(&self) -> usize {
+		self.opaque.len() + self.blended.len()
+	}
+
+	/// Checks if the list is empty.
+	pub fn is_empty(&self) -> bool {
+		self.opaque.is_empty() && self.blended.is_empty()
+	}
+
+	/// Remove every object from the list, keeping the allocated memory.
+	pub fn clear(&mut self) {
+		self.opaque.clear();
+		self.blended.clear();
+	}
+
+	/// Add an object to the list.
+	pub fn push(&mut self, object: Id<T>, key: Key, blended: bool) {
+		let draw = Draw { object, key };
+
+		if blended {
+			self.blended.push(draw)
+		} else {
+			self.opaque.push(draw)
+		}
+	}
+
+	/// Sort the list.
+	pub fn sort(&mut self) {
+		self.opaque.sort_unstable_by(|a, b| a.key.cmp_opaque(&b.key));
+		self.blended.sort_by(|a, b| a.key.cmp_blended(&b.key));
+	}
+
+	/// Iterate over the objects in drawing order.
+	///
+	/// The list must have been sorted before.
+	pub fn iter(&self) -> impl Iterator<Item=&Draw<T>> {
+		self.opaque.iter().chain(self.blended.iter())
+	}
+}
+
+impl<T> Default for DrawList<T> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
@@ -0,0 +1,161 @@
+//! Frames in flight.
+use std::sync::Arc;
+use magma::{
+	device,
+	Device,
+	DeviceOwned,
+	command,
+	pipeline,
+	sync::future::SignalFence
+};
+use crate::sync::{
+	FencePool,
+	CommandBufferPool,
+	command_buffer_pool
+};
+use super::target::swapchain::AcquiredImage;
+
+/// Default number of frames in flight.
+pub const DEFAULT_FRAMES_IN_FLIGHT: usize = 2;
+
+/// Ring of frames in flight.
+///
+/// Allows the CPU to record a frame while the GPU executes the previous ones.
+/// Each frame has its own command buffers and fence,
+/// so that they are not reused while in use by the GPU.
+/// The CPU only waits when it gets ahead of the GPU by the number of frames in the ring.
+pub struct Frames {
+	queue: device::Queue,
+	command_buffer_pool: CommandBufferPool,
+	fence_pool: FencePool,
+	frames: Vec<Frame>,
+	current: usize
+}
+
+impl Frames {
+	/// Create a new ring of `count` frames submitted to the given queue.
+	pub fn new(queue: device::Queue, count: usize) -> Self {
+		assert!(count > 0, "at least one frame is required");
+		let command_buffer_pool = CommandBufferPool::new(&queue).expect("unable to create command buffer pool");
+		let fence_pool = FencePool::new(queue.device());
+
+		Self {
+			queue,
+			command_buffer_pool,
+			fence_pool,
+			frames: (0..count).map(Frame::new).collect(),
+			current: 0
+		}
+	}
+
+	pub fn device(&self) -> &Arc<Device> {
+		self.queue.device()
+	}
+
+	/// Number of frames in flight.
+	pub fn len(&self) -> usize {
+		self.frames.len()
+	}
+
+	/// Begin the next frame.
+	///
+	/// If the frame was still in flight, waits for its commands to be executed first.
+	pub fn begin(&mut self) -> &mut Frame {
+		self.current = (self.current + 1) % self.frames.len();
+		let frame = &mut self.frames[self.current];
+		frame.wait();
+		frame
+	}
+
+	/// Current frame.
+	pub fn current(&mut self) -> &mut Frame {
+		&mut self.frames[self.current]
+	}
+
+	/// Allocate a command buffer to record the current frame.
+	pub fn command_buffer(&self) -> command_buffer_pool::Buffer {
+		self.command_buffer_pool.get().expect("unable to allocate command buffer")
+	}
+
+	/// Submit the recorded commands of the current frame.
+	pub fn submit<B: 'static + command::Buffer>(&mut self, commands: command::buffer::Recorded<B>) {
+		let fence = self.fence_pool.get().expect("unable to create fence");
+		let (_, future) = self.queue.submit(commands).then_signal_fence(fence).expect("unable to submit command buffer");
+		self.frames[self.current].future = Some(Box::new(future))
+	}
+
+	/// Submit the recorded commands of the current frame, rendering to the given swapchain image.
+	///
+	/// The commands wait for the image acquisition, and signal the end of the rendering
+	/// for the presentation.
+	pub fn submit_to<B: 'static + command::Buffer>(&mut self, commands: command::buffer::Recorded<B>, image: &AcquiredImage) {
+		let fence = self.fence_pool.get().expect("unable to create fence");
+		let (_, future) = self.queue.submit(commands)
+			.wait_for(image.acquired(), pipeline::Stage::ColorAttachmentOutput)
+			.then_signal_semaphore(image.rendered())
+			.then_signal_fence(fence)
+			.expect("unable to submit command buffer");
+		self.frames[self.current].future = Some(Box::new(future))
+	}
+
+	/// Wait for every frame in flight.
+	pub fn wait_idle(&mut self) {
+		for frame in &mut self.frames {
+			frame.wait()
+		}
+	}
+}
+
+impl Drop for Frames {
+	fn drop(&mut self) {
+		self.wait_idle()
+	}
+}
+
+/// Frame in flight.
+pub struct Frame {
+	index: usize,
+	future: Option<Box<dyn SignalFence>>,
+	on_complete: Vec<Box<dyn FnOnce()>>
+}
+
+impl Frame {
+	fn new(index: usize) -> Self {
+		Self {
+			index,
+			future: None,
+			on_complete: Vec::new()
+		}
+	}
+
+	/// Index of the frame in the ring.
+	pub fn index(&self) -> usize {
+		self.index
+	}
+
+	/// Checks if the last commands submitted for this frame have been executed.
+	pub fn is_complete(&self) -> bool {
+		match &self.future {
+			Some(future) => future.is_signaled().expect("fence error"),
+			None => true
+		}
+	}
+
+	/// Call the given function once the commands of this frame have been executed.
+	pub fn on_complete<F: 'static + FnOnce()>(&mut self, f: F) {
+		self.on_complete.push(Box::new(f))
+	}
+
+	/// Wait for the commands of this frame to be executed.
+	///
+	/// Blocks on the frame fence.
+	fn wait(&mut self) {
+		if let Some(future) = self.future.take() {
+			future.wait(None).expect("fence error")
+		}
+
+		for f in self.on_complete.drain(..) {
+			f()
+		}
+	}
+}
@@ -0,0 +1,36 @@
+use std::convert::TryInto;
+use magma::{
+	image,
+	mem::{
+		Allocator,
+		HostVisibleSlot,
+		buffer
+	}
+};
+
+/// Type erased allocator, used to bind render resources.
+pub(crate) trait Memory {
+	fn bind_image(&mut self, image: image::Unbound) -> image::Bound;
+
+	/// Bind the given buffer to host visible memory, and returns a pointer to it.
+	fn bind_host_visible_buffer(&mut self, buffer: buffer::Unbound) -> (buffer::Bound, *mut u8);
+}
+
+impl<A: Allocator> Memory for A {
+	fn bind_image(&mut self, image: image::Unbound) -> image::Bound {
+		let slot = self.allocate(image.memory_requirements());
+		match unsafe { image.bind(slot) } {
+			Ok(bound) => bound,
+			Err((_, e)) => panic!("unable to bind image memory: {:?}", e)
+		}
+	}
+
+	fn bind_host_visible_buffer(&mut self, buffer: buffer::Unbound) -> (buffer::Bound, *mut u8) {
+		let slot: A::HostVisibleSlot = self.allocate(buffer.memory_requirements()).try_into().ok().expect("memory is not host visible");
+		let ptr = slot.ptr().expect("unable to map buffer memory") as *mut u8;
+		match unsafe { buffer.bind(slot) } {
+			Ok(bound) => (bound, ptr),
+			Err((_, e)) => panic!("unable to bind buffer memory: {:?}", e)
+		}
+	}
+}
@@ -1,12 +1,23 @@
 use std::{
+	hash::Hash,
+	cell::RefCell,
 	sync::Arc,
 	ops::Deref,
 	marker::PhantomData
 };
 use magma::{
 	device,
+	command::{
+		self,
+		Buffer as _
+	},
+	pipeline,
 	Device,
-	framebuffer::RenderPass
+	mem::Allocator,
+	framebuffer::{
+		RenderPass,
+		Framebuffer
+	}
 };
 use ::scene::{
 	Scene,
@@ -16,49 +27,274 @@ use ::scene::{
 };
 use crate::{
 	View,
-	sync::Loader
+	view::geometry::projection::CameraProjection,
+	sync::{
+		self,
+		Loader
+	}
 };
 
 mod target;
 mod context;
 mod pov;
 mod generator;
+mod memory;
+mod readback;
+pub mod frames;
+pub mod draw_list;
+pub mod occlusion;
+pub mod picking;
 
-pub use target::Target;
+pub use target::{
+	Target,
+	OffscreenTarget,
+	SwapchainTarget
+};
 pub use context::Context;
 pub use generator::Generator;
-pub use pov::PointOfView;
+pub use pov::{
+	PointOfView,
+	FrustumPointOfView
+};
+pub use draw_list::DrawList;
+pub use occlusion::{
+	OcclusionCulling,
+	OcclusionPointOfView
+};
+pub use frames::{
+	Frames,
+	Frame
+};
+pub use picking::{
+	Picker,
+	IdPass
+};
 
 pub struct Worker<R: Target, T, E, P: PointOfView<T, E>, G: Generator<T>> {
 	inner: Inner<R, T, E, G>,
-	point_of_view: P
+	point_of_view: P,
+	picking: Option<IdPass<T>>,
+
+	/// Loader sharing the graphics queue, recording its uploads in each frame.
+	inline_loader: Option<Box<dyn InlineLoader>>,
+
+	/// Target extent during the last cycle.
+	extent: Option<(u32, u32)>,
+	resize_listeners: Vec<Box<dyn FnMut(u32, u32)>>
 }
 
 impl<R: Target, T, E, P: PointOfView<T, E>, G: Generator<T>> Worker<R, T, E, P, G> {
-	pub fn new(render_target: R, point_of_view: P, generator: G) -> Self {
+	pub fn new(render_target: R, graphics_queue: device::Queue, loader: Loader, point_of_view: P, generator: G) -> Self {
 		Self {
 			inner: Inner {
 				context: WorkerContext {
 					target: render_target,
-					graphics_queue: panic!("TODO"),
-					loader: panic!("TODO")
+					graphics_queue,
+					loader,
+					on_frame_complete: RefCell::new(Vec::new())
 				},
 				generator,
 				views: Map::new(),
+				draw_list: DrawList::new(),
 				e: PhantomData
 			},
-			point_of_view
+			point_of_view,
+			picking: None,
+			inline_loader: None,
+			extent: None,
+			resize_listeners: Vec::new()
+		}
+	}
+
+	/// Render target.
+	pub fn target(&self) -> &R {
+		&self.inner.context.target
+	}
+
+	/// Mutable render target.
+	pub fn target_mut(&mut self) -> &mut R {
+		&mut self.inner.context.target
+	}
+
+	/// Record the uploads of the given inline loader at the beginning of each frame
+	/// rendered with [`Worker::render_frame`].
+	///
+	/// Used when the loader shares the graphics queue (see [`Loader::inline`]).
+	pub fn set_inline_loader<A: 'static + Allocator>(&mut self, inline_loader: sync::loader::Inline<A>) {
+		self.inline_loader = Some(Box::new(inline_loader))
+	}
+
+	/// Checks if some resources drawn by the last cycle are still being loaded.
+	///
+	/// This includes the uploads waiting to be recorded by the inline loader,
+	/// and the geometry buffers requested by the views of the draw list.
+	pub fn is_loading(&self) -> bool {
+		self.inline_loader.as_ref().map_or(false, |inline_loader| inline_loader.is_pending())
+			|| self.inner.is_loading()
+	}
+
+	/// Register a function called with the new target size each time the target is resized.
+	///
+	/// Resizes are detected at the beginning of each cycle,
+	/// and the first cycle is considered as a resize.
+	pub fn on_resize<F: 'static + FnMut(u32, u32)>(&mut self, f: F) {
+		self.resize_listeners.push(Box::new(f))
+	}
+
+	/// Check if the target has been resized since the last cycle, and notify the listeners.
+	fn check_extent(&mut self) {
+		let extent = self.inner.context.target.extent();
+		if self.extent != Some(extent) {
+			self.extent = Some(extent);
+			let (width, height) = extent;
+
+			self.point_of_view.resize(width, height);
+
+			if let Some(picking) = &mut self.picking {
+				picking.resize(extent)
+			}
+
+			for f in &mut self.resize_listeners {
+				f(width, height)
+			}
+		}
+	}
+
+	/// Enable the picking stage, with the given id pass.
+	///
+	/// When picking requests are pending, visible objects are drawn in the id buffer
+	/// after the main pass.
+	pub fn enable_picking(&mut self, mut id_pass: IdPass<T>) {
+		if let Some(extent) = self.extent {
+			id_pass.resize(extent)
 		}
+
+		self.picking = Some(id_pass)
+	}
+
+	/// Disable the picking stage, returning its id pass.
+	pub fn disable_picking(&mut self) -> Option<IdPass<T>> {
+		self.picking.take()
+	}
+
+	/// Picking stage, if enabled.
+	pub fn picking_mut(&mut self) -> Option<&mut IdPass<T>> {
+		self.picking.as_mut()
 	}
 }
 
-impl<R: Target, T, E, P: PointOfView<T, E>, G: Generator<T>> cycles::Worker<Scene<T, E>> for Worker<R, T, E, P, G> {
+impl<R: Target, T, E, P: PointOfView<T, E>, G: Generator<T>> Worker<R, T, E, P, G> where Id<T>: Copy + PartialEq {
+	/// Record the draw list prepared by the last cycle in the given framebuffer.
+	///
+	/// The framebuffer must be compatible with the render pass of the target,
+	/// and have the extent of the target.
+	/// The viewport and scissor are set to cover the whole target.
+	/// The camera view (`modelview`) and projection matrices are pushed to every drawn view.
+	/// If the picking stage is enabled and some requests are pending,
+	/// the id pass is recorded after the main pass.
+	pub fn record<B: command::Buffer>(&mut self, commands: &mut command::buffer::Recorder<B>, framebuffer: &Framebuffer, projection: &CameraProjection) {
+		self.inner.context.loader.acquisitions().record(commands, &self.inner.context.graphics_queue);
+
+		let extent = self.inner.context.target.extent();
+		commands.begin_render_pass(self.inner.context.target.render_pass(), framebuffer, extent, &[
+			command::buffer::ClearValue::Color([0.0, 0.0, 0.0, 1.0].into()),
+			command::buffer::ClearValue::DepthStencil(1.0, 0)
+		]);
+		commands.set_viewport(0, &[pipeline::Viewport::new(extent)]);
+		commands.set_scissor(0, &[pipeline::Scissor::new(extent)]);
+		self.inner.render(commands, projection);
+		commands.end_render_pass();
+
+		if let Some(picking) = &mut self.picking {
+			self.inner.render_ids(commands, projection, picking)
+		}
+	}
+
+	/// Record and submit the draw list prepared by the last cycle, as the next frame of the given ring.
+	///
+	/// Waits only if the ring is full of frames in flight.
+	/// Picking requests recorded in this frame are resolved once the frame has been executed.
+	pub fn render_frame(&mut self, frames: &mut Frames, framebuffer: &Framebuffer, projection: &CameraProjection) where T: 'static {
+		let recorded = self.record_frame(frames, framebuffer, projection);
+		frames.submit(recorded)
+	}
+
+	/// Begin the next frame of the given ring, and record it.
+	fn record_frame(&mut self, frames: &mut Frames, framebuffer: &Framebuffer, projection: &CameraProjection) -> command::buffer::Recorded<sync::command_buffer_pool::Buffer> where T: 'static {
+		frames.begin();
+
+		let mut depth_readback = None;
+		let command_buffer = frames.command_buffer();
+		let recorded = command_buffer.record(|commands| {
+			if let Some(inline_loader) = &mut self.inline_loader {
+				inline_loader.record(commands)
+			}
+
+			self.record(commands, framebuffer, projection);
+
+			if let Some(occlusion_culling) = self.point_of_view.occlusion_culling_mut() {
+				depth_readback = occlusion_culling.record_readback(&self.inner.context.target, commands, projection.proj * projection.modelview)
+			}
+		}).expect("unable to record command buffer");
+
+		for f in self.inner.context.on_frame_complete.borrow_mut().drain(..) {
+			frames.current().on_complete(f)
+		}
+
+		if let Some(executed) = depth_readback {
+			frames.current().on_complete(move || executed.store(true, std::sync::atomic::Ordering::Release))
+		}
+
+		if let Some(picking) = &mut self.picking {
+			let readbacks = picking.take_recorded();
+			if !readbacks.is_empty() {
+				frames.current().on_complete(move || unsafe {
+					// Safe because the frame has been executed.
+					readbacks.resolve()
+				})
+			}
+		}
+
+		recorded
+	}
+}
+
+impl<T, E, P: PointOfView<T, E>, G: Generator<T>> Worker<SwapchainTarget, T, E, P, G> where T: 'static, Id<T>: Copy + PartialEq {
+	/// Render the draw list prepared by the last cycle in the next swapchain image, and present it.
+	///
+	/// Does nothing if no image can be acquired (for instance when the window is minimized).
+	/// If the swapchain must be recreated, waits for every frame in flight first.
+	pub fn present_frame(&mut self, frames: &mut Frames, projection: &CameraProjection) {
+		if self.inner.context.target.needs_rebuild() {
+			frames.wait_idle()
+		}
+
+		if let Some(image) = self.inner.context.target.acquire() {
+			let framebuffer = image.framebuffer().clone();
+			let recorded = self.record_frame(frames, &framebuffer, projection);
+			frames.submit_to(recorded, &image);
+			self.inner.context.target.present(image)
+		}
+	}
+}
+
+impl<R: Target, T, E, P: PointOfView<T, E>, G: Generator<T>> cycles::Worker<Scene<T, E>> for Worker<R, T, E, P, G> where Id<T>: Copy + Eq + Hash {
 	fn cycle(&mut self, scene: &Scene<T, E>) {
+		self.check_extent();
+
+		if let Some(occlusion_culling) = self.point_of_view.occlusion_culling_mut() {
+			occlusion_culling.update_depth(&self.inner.context.target)
+		}
+
 		self.point_of_view.cycle(scene);
+
+		self.inner.draw_list.clear();
 		for id in self.point_of_view.visible_objects() {
 			let object = scene.get(id);
-			self.inner.render_object(scene, object);
+			let distance = self.point_of_view.distance(id);
+			self.inner.prepare_object(object, distance);
 		}
+		self.inner.draw_list.sort();
 	}
 
 	fn apply(&mut self, _scene: &mut Scene<T, E>) {
@@ -70,14 +306,14 @@ struct Inner<R: Target, T, E, G: Generator<T>> {
 	context: WorkerContext<R>,
 	generator: G,
 	views: Map<T, View>,
+	draw_list: DrawList<T>,
 	e: PhantomData<E>
 }
 
 impl<R: Target, T, E, G: Generator<T>> Inner<R, T, E, G> {
-	fn render_object(&mut self, scene: &Scene<T, E>, object: Ref<T>) {
-		let view = self.views.get(object.id());
-		
-		let view = match view {
+	/// Make sure the view of the given object is generated, and add it to the draw list.
+	fn prepare_object(&mut self, object: Ref<T>, distance: f32) {
+		let view = match self.views.get(object.id()) {
 			Some(view) => view,
 			None => {
 				let view = self.generator.view(&object);
@@ -86,15 +322,71 @@ impl<R: Target, T, E, G: Generator<T>> Inner<R, T, E, G> {
 			}
 		};
 
-		panic!("TODO")
-		// view.draw(&self.context, commands, projection)
+		let (pipeline, geometry) = view.state(&self.context.target);
+		let key = draw_list::Key {
+			pipeline,
+			geometry,
+			distance
+		};
+
+		self.draw_list.push(object.id(), key, view.is_blended());
+	}
+
+	/// Checks if some views of the draw list are still being loaded.
+	fn is_loading(&self) -> bool {
+		self.draw_list.iter().any(|draw| {
+			self.views.get(draw.object).map_or(false, View::is_loading)
+		})
+	}
+
+	/// Draw every object of the draw list, in order.
+	fn render<B: command::Buffer>(&self, commands: &mut command::buffer::Recorder<B>, projection: &CameraProjection) {
+		for draw in self.draw_list.iter() {
+			if let Some(view) = self.views.get(draw.object) {
+				view.draw(&self.context, commands, projection)
+			}
+		}
+	}
+
+	/// Draw the id of every object of the draw list, if some picking requests are pending.
+	fn render_ids<B: command::Buffer>(&self, commands: &mut command::buffer::Recorder<B>, projection: &CameraProjection, id_pass: &mut IdPass<T>) where Id<T>: Copy + PartialEq {
+		if id_pass.is_requested() {
+			let objects = self.draw_list.iter().filter_map(|draw| {
+				self.views.get(draw.object).map(|view| (draw.object, view))
+			});
+
+			id_pass.record(&self.context, commands, projection, objects)
+		}
+	}
+}
+
+/// Type erased inline loader.
+trait InlineLoader {
+	fn is_pending(&self) -> bool;
+
+	fn record(&mut self, commands: &mut command::buffer::Recorder<sync::command_buffer_pool::Buffer>);
+}
+
+impl<A: Allocator> InlineLoader for sync::loader::Inline<A> {
+	fn is_pending(&self) -> bool {
+		sync::loader::Inline::is_pending(self)
+	}
+
+	fn record(&mut self, commands: &mut command::buffer::Recorder<sync::command_buffer_pool::Buffer>) {
+		sync::loader::Inline::record(self, commands)
 	}
 }
 
 struct WorkerContext<R: Target> {
 	target: R,
 	graphics_queue: device::Queue,
-	loader: Loader
+	loader: Loader,
+
+	/// Functions to call once the frame being recorded has been executed.
+	///
+	/// Functions registered while recording outside of a frame ring
+	/// are attached to the next frame.
+	on_frame_complete: RefCell<Vec<Box<dyn FnOnce()>>>
 }
 
 impl<R: Target> Context for WorkerContext<R> {
@@ -111,4 +403,8 @@ impl<R: Target> Context for WorkerContext<R> {
 	fn loader(&self) -> &Loader {
 		&self.loader
 	}
+
+	fn on_frame_complete(&self, f: Box<dyn FnOnce()>) {
+		self.on_frame_complete.borrow_mut().push(f)
+	}
 }
\ No newline at end of file
@@ -0,0 +1,421 @@
+//! Hierarchical-Z occlusion culling.
+use std::{
+	hash::Hash,
+	collections::HashSet,
+	sync::{
+		Arc,
+		atomic::{
+			AtomicBool,
+			Ordering
+		}
+	}
+};
+use glam::{
+	Vec2,
+	Vec4,
+	Mat4
+};
+use magma::command;
+use scene::{
+	Scene,
+	Id
+};
+use crate::bounds::Volume;
+use super::{
+	Target,
+	PointOfView
+};
+
+/// Depth pyramid.
+///
+/// Each level is half the size of the previous one,
+/// and each of its texels stores the farthest depth of the four texels below it:
+/// the greatest depth, or the smallest with reversed-Z.
+pub struct DepthPyramid {
+	levels: Vec<Level>,
+	reversed_z: bool
+}
+
+/// Farthest of the two given depths.
+fn farthest(a: f32, b: f32, reversed_z: bool) -> f32 {
+	if reversed_z {
+		a.min(b)
+	} else {
+		a.max(b)
+	}
+}
+
+struct Level {
+	width: u32,
+	height: u32,
+	depths: Vec<f32>
+}
+
+impl Level {
+	fn get(&self, x: u32, y: u32) -> f32 {
+		let x = std::cmp::min(x, self.width - 1);
+		let y = std::cmp::min(y, self.height - 1);
+		self.depths[(y * self.width + x) as usize]
+	}
+
+	/// Build the next (smaller) level.
+	fn reduce(&self, reversed_z: bool) -> Level {
+		let width = std::cmp::max(1, (self.width + 1) / 2);
+		let height = std::cmp::max(1, (self.height + 1) / 2);
+		let mut depths = Vec::with_capacity((width * height) as usize);
+
+		for y in 0..height {
+			for x in 0..width {
+				let depth = farthest(
+					farthest(self.get(2 * x, 2 * y), self.get(2 * x + 1, 2 * y), reversed_z),
+					farthest(self.get(2 * x, 2 * y + 1), self.get(2 * x + 1, 2 * y + 1), reversed_z),
+					reversed_z
+				);
+				depths.push(depth)
+			}
+		}
+
+		Level {
+			width,
+			height,
+			depths
+		}
+	}
+}
+
+impl DepthPyramid {
+	/// Build the pyramid of the given depth buffer.
+	///
+	/// Depth values are given row by row, starting from the top-left corner.
+	/// If `reversed_z` is set, the depth is `1` on the near plane and `0` on the far plane.
+	pub fn new(depths: &[f32], width: u32, height: u32, reversed_z: bool) -> Self {
+		assert!(width > 0 && height > 0);
+		assert_eq!(depths.len(), (width * height) as usize);
+
+		let mut levels = vec![Level {
+			width,
+			height,
+			depths: depths.to_vec()
+		}];
+
+		loop {
+			let last = levels.last().unwrap();
+			if last.width == 1 && last.height == 1 {
+				break
+			}
+
+			let next = last.reduce(reversed_z);
+			levels.push(next)
+		}
+
+		Self {
+			levels,
+			reversed_z
+		}
+	}
+
+	/// Width of the base level.
+	pub fn width(&self) -> u32 {
+		self.levels[0].width
+	}
+
+	/// Height of the base level.
+	pub fn height(&self) -> u32 {
+		self.levels[0].height
+	}
+
+	/// Number of levels.
+	pub fn level_count(&self) -> usize {
+		self.levels.len()
+	}
+
+	/// Checks if the depth is reversed.
+	pub fn is_reversed_z(&self) -> bool {
+		self.reversed_z
+	}
+
+	/// Checks if the depth `a` is strictly nearer than the depth `b`.
+	pub fn is_nearer(&self, a: f32, b: f32) -> bool {
+		if self.reversed_z {
+			a > b
+		} else {
+			a < b
+		}
+	}
+
+	/// Farthest depth in the given rectangle, in normalized `[0, 1]` screen coordinates.
+	///
+	/// The result is conservative: it may cover slightly more than the given rectangle.
+	pub fn farthest_depth(&self, min: Vec2, max: Vec2) -> f32 {
+		let min = min.max(Vec2::zero()).min(Vec2::one());
+		let max = max.max(Vec2::zero()).min(Vec2::one());
+
+		// Choose the level where the rectangle covers at most 2x2 texels.
+		let size = (max - min) * Vec2::new(self.width() as f32, self.height() as f32);
+		let extent = size.x.max(size.y).max(1.0);
+		let level = std::cmp::min(extent.log2().ceil() as usize, self.levels.len() - 1);
+		let level = &self.levels[level];
+
+		let scale = Vec2::new(level.width as f32, level.height as f32);
+		let (x0, y0) = ((min.x * scale.x) as u32, (min.y * scale.y) as u32);
+		let (x1, y1) = ((max.x * scale.x) as u32, (max.y * scale.y) as u32);
+
+		let mut depth = level.get(x0, y0);
+		for y in y0..=y1 {
+			for x in x0..=x1 {
+				depth = farthest(depth, level.get(x, y), self.reversed_z)
+			}
+		}
+
+		depth
+	}
+}
+
+/// Occlusion culling stage.
+///
+/// Tests the bounding volumes of the objects against the depth pyramid of the previous frame.
+/// Objects that were not known in the previous frame (newly visible objects)
+/// are never culled, since the previous depth may not account for what is in front of them.
+///
+/// When used by the point of view of the render worker (see [`OcclusionPointOfView`]),
+/// the depth of the rendered frames is copied back from targets supporting it
+/// (see [`Target::record_depth_readback`]), and given to the stage once the frame has been executed.
+/// Otherwise, it must be provided through [`OcclusionCulling::set_depth`].
+pub struct OcclusionCulling<T> {
+	/// Depth pyramid of the previous frame, with the view-projection used to render it.
+	pyramid: Option<(DepthPyramid, Mat4)>,
+
+	/// Checks if the depth is reversed.
+	reversed_z: bool,
+
+	/// Depth readback in flight, with the view-projection of its frame,
+	/// and a flag set once the frame has been executed.
+	readback: Option<(Mat4, Arc<AtomicBool>)>,
+
+	/// Objects tested during the previous frame.
+	previous: HashSet<Id<T>>,
+
+	/// Objects tested during the current frame.
+	current: HashSet<Id<T>>
+}
+
+impl<T> OcclusionCulling<T> where Id<T>: Copy + Eq + Hash {
+	/// Create a new occlusion culling stage.
+	///
+	/// `reversed_z` must match the camera projection (see [`Projection::is_reversed_z`](crate::camera::Projection::is_reversed_z)).
+	pub fn new(reversed_z: bool) -> Self {
+		Self {
+			pyramid: None,
+			reversed_z,
+			readback: None,
+			previous: HashSet::new(),
+			current: HashSet::new()
+		}
+	}
+
+	/// Checks if the depth is reversed.
+	pub fn is_reversed_z(&self) -> bool {
+		self.reversed_z
+	}
+
+	/// Set the depth of the previous frame.
+	///
+	/// The given view-projection matrix must be the one used to render this depth.
+	pub fn set_depth(&mut self, depths: &[f32], width: u32, height: u32, view_projection: Mat4) {
+		self.pyramid = Some((DepthPyramid::new(depths, width, height, self.reversed_z), view_projection))
+	}
+
+	/// Forget the depth of the previous frame.
+	///
+	/// Until a new depth is given, no object is culled.
+	pub fn reset(&mut self) {
+		self.pyramid = None;
+		self.readback = None
+	}
+
+	/// Checks if the given object, with the given world-space bounding volume, may be visible.
+	pub fn test(&mut self, object: Id<T>, volume: &Volume) -> bool {
+		let known = self.previous.contains(&object);
+		self.current.insert(object);
+
+		if !known {
+			return true
+		}
+
+		match &self.pyramid {
+			Some((pyramid, view_projection)) => !Self::is_occluded(pyramid, view_projection, volume),
+			None => true
+		}
+	}
+
+	/// Must be called at the end of each frame.
+	pub fn end_frame(&mut self) {
+		std::mem::swap(&mut self.previous, &mut self.current);
+		self.current.clear()
+	}
+
+	fn is_occluded(pyramid: &DepthPyramid, view_projection: &Mat4, volume: &Volume) -> bool {
+		let mut min = Vec2::splat(std::f32::INFINITY);
+		let mut max = Vec2::splat(std::f32::NEG_INFINITY);
+		let mut nearest = None;
+
+		for corner in volume.aabb().corners().iter() {
+			let clip = *view_projection * Vec4::new(corner.x, corner.y, corner.z, 1.0);
+
+			if clip.w <= 0.0 {
+				// The volume crosses the near plane.
+				return false
+			}
+
+			let ndc = clip.truncate() / clip.w;
+			let screen = Vec2::new(ndc.x, ndc.y) * 0.5 + Vec2::splat(0.5);
+			min = min.min(screen);
+			max = max.max(screen);
+			nearest = match nearest {
+				Some(depth) if !pyramid.is_nearer(ndc.z, depth) => Some(depth),
+				_ => Some(ndc.z)
+			};
+		}
+
+		if min.x < 0.0 || min.y < 0.0 || max.x > 1.0 || max.y > 1.0 {
+			// (Partially) outside of the previous frame, nothing is known.
+			return false
+		}
+
+		match nearest {
+			Some(nearest) => pyramid.is_nearer(pyramid.farthest_depth(min, max), nearest),
+			None => false
+		}
+	}
+}
+
+impl<T> OcclusionCulling<T> {
+	/// Record the depth readback of the frame rendered with the given view-projection,
+	/// unless a readback is already in flight.
+	///
+	/// Returns the flag to set once the frame has been executed.
+	pub(crate) fn record_readback<R: Target, B: command::Buffer>(
+		&mut self,
+		target: &R,
+		commands: &mut command::buffer::Recorder<B>,
+		view_projection: Mat4
+	) -> Option<Arc<AtomicBool>> {
+		if self.readback.is_none() && target.record_depth_readback(commands) {
+			let executed = Arc::new(AtomicBool::new(false));
+			self.readback = Some((view_projection, executed.clone()));
+			Some(executed)
+		} else {
+			None
+		}
+	}
+
+	/// Use the depth of the readback in flight, if its frame has been executed.
+	pub(crate) fn update_depth<R: Target>(&mut self, target: &R) {
+		let executed = match &self.readback {
+			Some((_, executed)) => executed.load(Ordering::Acquire),
+			None => false
+		};
+
+		if executed {
+			let (view_projection, _) = self.readback.take().unwrap();
+			let depth = unsafe {
+				// Safe because the readback has been executed.
+				target.read_depth()
+			};
+
+			if let Some((depths, width, height)) = depth {
+				self.pyramid = Some((DepthPyramid::new(&depths, width, height, self.reversed_z), view_projection))
+			}
+		}
+	}
+}
+
+impl<T> Default for OcclusionCulling<T> where Id<T>: Copy + Eq + Hash {
+	fn default() -> Self {
+		Self::new(false)
+	}
+}
+
+/// Point of view hiding the occluded objects of another point of view.
+///
+/// The visible objects of the wrapped point of view are tested by an [`OcclusionCulling`] stage,
+/// using their bounding volume.
+/// Occluded objects are then not visible to any user of the point of view,
+/// such as the draw list or the picking stage of the render worker.
+pub struct OcclusionPointOfView<T, P> {
+	point_of_view: P,
+	occlusion_culling: OcclusionCulling<T>,
+
+	/// Visible objects, computed during the last cycle.
+	visible: Vec<Id<T>>
+}
+
+impl<T, P> OcclusionPointOfView<T, P> where Id<T>: Copy + Eq + Hash {
+	/// Wrap the given point of view.
+	///
+	/// `reversed_z` must match the camera projection.
+	pub fn new(point_of_view: P, reversed_z: bool) -> Self {
+		Self {
+			point_of_view,
+			occlusion_culling: OcclusionCulling::new(reversed_z),
+			visible: Vec::new()
+		}
+	}
+
+	/// Wrapped point of view.
+	pub fn inner(&self) -> &P {
+		&self.point_of_view
+	}
+
+	/// Wrapped point of view.
+	pub fn inner_mut(&mut self) -> &mut P {
+		&mut self.point_of_view
+	}
+
+	/// Occlusion culling stage.
+	pub fn occlusion_culling(&self) -> &OcclusionCulling<T> {
+		&self.occlusion_culling
+	}
+}
+
+impl<T, E, P: PointOfView<T, E>> PointOfView<T, E> for OcclusionPointOfView<T, P> where Id<T>: Copy + Eq + Hash {
+	type Iter<'a> where T: 'a = std::slice::Iter<'a, Id<T>>;
+
+	fn cycle(&mut self, scene: &Scene<T, E>) {
+		self.point_of_view.cycle(scene);
+
+		self.visible.clear();
+		for id in self.point_of_view.visible_objects() {
+			let visible = match self.point_of_view.volume(id) {
+				Some(volume) => self.occlusion_culling.test(*id, &volume),
+				None => true
+			};
+
+			if visible {
+				self.visible.push(*id)
+			}
+		}
+
+		self.occlusion_culling.end_frame()
+	}
+
+	fn visible_objects<'a>(&'a self) -> Self::Iter<'a> {
+		self.visible.iter()
+	}
+
+	fn distance(&self, object: &Id<T>) -> f32 {
+		self.point_of_view.distance(object)
+	}
+
+	fn volume(&self, object: &Id<T>) -> Option<Volume> {
+		self.point_of_view.volume(object)
+	}
+
+	fn resize(&mut self, width: u32, height: u32) {
+		self.point_of_view.resize(width, height)
+	}
+
+	fn occlusion_culling_mut(&mut self) -> Option<&mut OcclusionCulling<T>> {
+		Some(&mut self.occlusion_culling)
+	}
+}
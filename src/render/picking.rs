@@ -0,0 +1,411 @@
+//! GPU picking.
+//!
+//! Visible objects are drawn in an id buffer, where each pixel stores the index of the
+//! object covering it (plus one, zero meaning no object).
+//! Picking requests are served by copying the requested region of this buffer into
+//! host visible memory, which is read once the frame commands have been executed.
+//!
+//! The object index is pushed after the projection matrices, beyond the 128 bytes of push constants
+//! guaranteed by Vulkan. On devices without room for it, the id pass is unavailable
+//! and CPU [picking](crate::picking) must be used instead.
+use std::sync::Arc;
+use crossbeam_channel::{
+	Sender,
+	Receiver
+};
+use magma::{
+	Device,
+	DeviceOwned,
+	command,
+	format::Format,
+	framebuffer::{
+		self,
+		RenderPass,
+		Framebuffer
+	},
+	image,
+	mem::{
+		Allocator,
+		buffer
+	}
+};
+use scene::Id;
+use crate::{
+	View,
+	view::{
+		material::FragmentShader,
+		geometry::projection::CameraProjection,
+		object::ID_PUSH_CONSTANT_OFFSET
+	},
+	sync::loader::{
+		Loading,
+		loading
+	}
+};
+use super::{
+	Context,
+	memory::Memory,
+	readback
+};
+
+/// Push constants size required by the id pipelines.
+const ID_PUSH_CONSTANTS_END: u32 = ID_PUSH_CONSTANT_OFFSET + std::mem::size_of::<u32>() as u32;
+
+/// Picked region, in pixels.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Rect {
+	pub x: u32,
+	pub y: u32,
+	pub width: u32,
+	pub height: u32
+}
+
+impl Rect {
+	pub fn new(x: u32, y: u32, width: u32, height: u32) -> Self {
+		Self {
+			x,
+			y,
+			width,
+			height
+		}
+	}
+
+	/// Clip the rectangle to the given extent.
+	///
+	/// Returns `None` if the intersection is empty.
+	fn clipped(&self, (width, height): (u32, u32)) -> Option<Rect> {
+		let x = std::cmp::min(self.x, width);
+		let y = std::cmp::min(self.y, height);
+		let w = std::cmp::min(self.x.saturating_add(self.width), width) - x;
+		let h = std::cmp::min(self.y.saturating_add(self.height), height) - y;
+
+		if w > 0 && h > 0 {
+			Some(Rect::new(x, y, w, h))
+		} else {
+			None
+		}
+	}
+}
+
+struct Request<T> {
+	rect: Rect,
+	objects: loading::Handle<Vec<Id<T>>>
+}
+
+/// Picking requests sender.
+pub struct Picker<T> {
+	channel: Sender<Request<T>>
+}
+
+impl<T> Clone for Picker<T> {
+	fn clone(&self) -> Self {
+		Self {
+			channel: self.channel.clone()
+		}
+	}
+}
+
+impl<T: 'static> Picker<T> where Id<T>: Send + Sync {
+	/// Pick the object at the given pixel.
+	///
+	/// The result is available once the next frame has been rendered.
+	pub fn pick(&self, x: u32, y: u32) -> Loading<Option<Id<T>>> {
+		let (loading, handle) = Loading::mapped(|objects: Vec<Id<T>>| objects.into_iter().next());
+		self.send(Rect::new(x, y, 1, 1), handle);
+		loading
+	}
+
+	/// Pick every object visible in the given rectangle.
+	///
+	/// Each object appears once in the result.
+	pub fn pick_rect(&self, rect: Rect) -> Loading<Vec<Id<T>>> {
+		let (loading, handle) = Loading::new();
+		self.send(rect, handle);
+		loading
+	}
+
+	fn send(&self, rect: Rect, objects: loading::Handle<Vec<Id<T>>>) {
+		self.channel.send(Request {
+			rect,
+			objects
+		}).expect("unable to send picking request")
+	}
+}
+
+/// Id buffer attachments, for a given extent.
+struct Attachments {
+	ids: Arc<image::Bound>,
+	framebuffer: Framebuffer
+}
+
+/// Pending readback.
+struct Readback<T> {
+	request: Request<T>,
+	rect: Option<Rect>,
+	buffer: Option<(buffer::Bound, *mut u8)>,
+	objects: Arc<Vec<Id<T>>>
+}
+
+impl<T> Readback<T> where Id<T>: Copy + PartialEq {
+	/// Read the copied ids and resolve the request.
+	fn resolve(self) {
+		let mut objects = Vec::new();
+
+		if let (Some(rect), Some((buffer, ptr))) = (self.rect, &self.buffer) {
+			readback::invalidate(buffer);
+			let pixels = unsafe {
+				// Safe because the copy commands have been executed.
+				std::slice::from_raw_parts(*ptr as *const u32, (rect.width * rect.height) as usize)
+			};
+
+			for index in pixels {
+				// Ids out of the table (not written by this pass) are ignored.
+				if let Some(object) = (*index as usize).checked_sub(1).and_then(|i| self.objects.get(i)) {
+					if !objects.contains(object) {
+						objects.push(*object)
+					}
+				}
+			}
+		}
+
+		std::mem::drop(self.request.objects.prepare(objects))
+	}
+}
+
+/// Id buffer render pass.
+pub struct IdPass<T> {
+	requests: Receiver<Request<T>>,
+	memory: Box<dyn Memory>,
+	render_pass: Arc<RenderPass>,
+	shader: FragmentShader,
+	extent: (u32, u32),
+	attachments: Option<Attachments>,
+	readbacks: Vec<Readback<T>>
+}
+
+impl<T> IdPass<T> where Id<T>: Copy + PartialEq {
+	/// Create a new id pass with the given extent, and its requests sender.
+	///
+	/// The shader must be compiled from `view/material/shaders/id.frag`.
+	/// Id and depth buffers, and readback buffers are allocated with the given allocator,
+	/// which must provide host visible memory.
+	///
+	/// Returns `None` if the device push constants cannot hold the object index.
+	pub fn new<A: 'static + Allocator>(device: &Arc<Device>, allocator: A, shader: FragmentShader, extent: (u32, u32)) -> Option<(Self, Picker<T>)> {
+		let max_push_constants_size = device.physical_device().limits().max_push_constants_size;
+		if max_push_constants_size < ID_PUSH_CONSTANTS_END {
+			log::warn!("id pass unavailable: {} bytes of push constants, {} required", max_push_constants_size, ID_PUSH_CONSTANTS_END);
+			return None
+		}
+
+		let (sender, receiver) = crossbeam_channel::unbounded();
+
+		let render_pass = RenderPass::new(
+			device,
+			&[
+				framebuffer::render_pass::Attachment::new(
+					Format::R32Uint,
+					framebuffer::render_pass::LoadOp::Clear,
+					framebuffer::render_pass::StoreOp::Store,
+					image::Layout::Undefined,
+					image::Layout::TransferSrcOptimal
+				),
+				framebuffer::render_pass::Attachment::new(
+					Format::D32Sfloat,
+					framebuffer::render_pass::LoadOp::Clear,
+					framebuffer::render_pass::StoreOp::DontCare,
+					image::Layout::Undefined,
+					image::Layout::DepthStencilAttachmentOptimal
+				)
+			],
+			&[framebuffer::render_pass::Subpass::new(&[0], Some(1))]
+		).expect("unable to create id render pass");
+
+		let pass = Self {
+			requests: receiver,
+			memory: Box::new(allocator),
+			render_pass: Arc::new(render_pass),
+			shader,
+			extent,
+			attachments: None,
+			readbacks: Vec::new()
+		};
+
+		let picker = Picker {
+			channel: sender
+		};
+
+		Some((pass, picker))
+	}
+
+	/// Id buffer extent.
+	pub fn extent(&self) -> (u32, u32) {
+		self.extent
+	}
+
+	/// Resize the id buffer.
+	///
+	/// Should follow the size of the render target so that picking coordinates match.
+	pub fn resize(&mut self, extent: (u32, u32)) {
+		if self.extent != extent {
+			self.extent = extent;
+			self.attachments = None
+		}
+	}
+
+	/// Render pass used to draw the ids.
+	pub fn render_pass(&self) -> &Arc<RenderPass> {
+		&self.render_pass
+	}
+
+	fn attachments<C: Context>(&mut self, context: &C) -> &Attachments {
+		let extent = self.extent;
+		if self.attachments.is_none() {
+			let device = self.render_pass.device();
+
+			let ids = Arc::new(self.memory.bind_image(image::Unbound::new(
+				device,
+				image::Type::D2,
+				Format::R32Uint,
+				extent,
+				image::Usage::ColorAttachment | image::Usage::TransferSource,
+				context.graphics_queue()
+			).expect("unable to create id image")));
+
+			let depth = Arc::new(self.memory.bind_image(image::Unbound::new(
+				device,
+				image::Type::D2,
+				Format::D32Sfloat,
+				extent,
+				image::Usage::DepthStencilAttachment,
+				context.graphics_queue()
+			).expect("unable to create depth image")));
+
+			let framebuffer = Framebuffer::new(
+				&self.render_pass,
+				vec![ids.view(), depth.view()],
+				extent
+			).expect("unable to create id framebuffer");
+
+			self.attachments = Some(Attachments {
+				ids,
+				framebuffer
+			})
+		}
+
+		self.attachments.as_ref().unwrap()
+	}
+
+	/// Checks if some picking requests are waiting to be recorded.
+	pub fn is_requested(&self) -> bool {
+		!self.requests.is_empty()
+	}
+
+	/// Record the id pass for the given objects, if some picking requests are pending.
+	///
+	/// The recorded requests are resolved by [`IdPass::resolve`],
+	/// which must be called once the recorded commands have been executed.
+	pub fn record<'a, C: Context, B: command::Buffer, I: IntoIterator<Item = (Id<T>, &'a View)>>(
+		&mut self,
+		context: &C,
+		commands: &mut command::buffer::Recorder<B>,
+		projection: &CameraProjection,
+		objects: I
+	) {
+		let requests: Vec<_> = self.requests.try_iter().collect();
+		if requests.is_empty() {
+			return
+		}
+
+		let render_pass = self.render_pass.clone();
+		let extent = self.extent;
+		let attachments = self.attachments(context);
+		let ids = attachments.ids.clone();
+
+		commands.begin_render_pass(&render_pass, &attachments.framebuffer, extent, &[
+			command::buffer::ClearValue::Color([0, 0, 0, 0].into()),
+			command::buffer::ClearValue::DepthStencil(1.0, 0)
+		]);
+		commands.set_viewport(0, &[magma::pipeline::Viewport::new(extent)]);
+		commands.set_scissor(0, &[magma::pipeline::Scissor::new(extent)]);
+
+		let mut table = Vec::new();
+		for (object, view) in objects {
+			table.push(object);
+			view.draw_id(context, commands, projection, &render_pass, &self.shader, table.len() as u32)
+		}
+
+		commands.end_render_pass();
+		readback::record_attachment_barrier(commands, &ids, image::Layout::TransferSrcOptimal, image::Aspect::Color);
+
+		let table = Arc::new(table);
+		for request in requests {
+			let rect = request.rect.clipped(extent);
+			let buffer = rect.map(|rect| {
+				let size = (rect.width * rect.height) as u64 * std::mem::size_of::<u32>() as u64;
+				let buffer = buffer::Unbound::new(
+					render_pass.device(),
+					size,
+					buffer::Usage::TransferDestination,
+					context.graphics_queue()
+				).expect("unable to create readback buffer");
+
+				let (buffer, ptr) = self.memory.bind_host_visible_buffer(buffer);
+
+				commands.copy_image_to_buffer(&ids, image::Layout::TransferSrcOptimal, &buffer, &[command::buffer::BufferImageCopy {
+					buffer_offset: 0,
+					buffer_row_length: 0,
+					buffer_image_height: 0,
+					image_offset: (rect.x as i32, rect.y as i32, 0),
+					image_extent: (rect.width, rect.height, 1)
+				}]);
+				readback::record_host_barrier(commands, &buffer, size);
+
+				(buffer, ptr)
+			});
+
+			self.readbacks.push(Readback {
+				request,
+				rect,
+				buffer,
+				objects: table.clone()
+			})
+		}
+	}
+
+	/// Take the picking requests recorded since the last call.
+	///
+	/// Used to resolve the requests of each frame separately.
+	pub fn take_recorded(&mut self) -> Readbacks<T> {
+		Readbacks(std::mem::take(&mut self.readbacks))
+	}
+
+	/// Resolve the recorded picking requests.
+	///
+	/// # Safety
+	///
+	/// The commands recorded by the last calls to [`IdPass::record`] must have been executed.
+	pub unsafe fn resolve(&mut self) {
+		self.take_recorded().resolve()
+	}
+}
+
+/// Recorded picking requests.
+pub struct Readbacks<T>(Vec<Readback<T>>);
+
+impl<T> Readbacks<T> where Id<T>: Copy + PartialEq {
+	pub fn is_empty(&self) -> bool {
+		self.0.is_empty()
+	}
+
+	/// Resolve the picking requests.
+	///
+	/// # Safety
+	///
+	/// The commands recording the requests must have been executed.
+	pub unsafe fn resolve(self) {
+		for readback in self.0 {
+			readback.resolve()
+		}
+	}
+}
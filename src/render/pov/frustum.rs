@@ -0,0 +1,171 @@
+use std::{
+	hash::Hash,
+	collections::HashMap
+};
+use glam::{
+	Vec3,
+	Mat4
+};
+use scene::{
+	Scene,
+	Id
+};
+use crate::{
+	bounds::{
+		Frustum,
+		Volume
+	},
+	camera::{
+		Camera,
+		Projection
+	}
+};
+use super::PointOfView;
+
+/// Frustum-culling point of view.
+///
+/// Objects are registered with their world-space bounding volume.
+/// At each cycle, only the objects whose bounding volume intersects the camera
+/// frustum are considered visible.
+pub struct FrustumPointOfView<T> {
+	/// View matrix (world to camera space).
+	view: Mat4,
+
+	/// Projection matrix (camera to clip space).
+	projection: Mat4,
+
+	/// Camera projection, adapted to the target size when it is resized.
+	camera_projection: Option<Box<dyn Projection>>,
+
+	/// Camera position in world space.
+	eye: Vec3,
+
+	/// Registered objects with their bounding volume.
+	objects: HashMap<Id<T>, Volume>,
+
+	/// Visible objects, computed during the last cycle.
+	visible: Vec<Id<T>>
+}
+
+impl<T> FrustumPointOfView<T> where Id<T>: Copy + Eq + Hash {
+	/// Create a new point of view from the given camera view and projection matrices.
+	pub fn new(view: Mat4, projection: Mat4) -> Self {
+		Self {
+			view,
+			projection,
+			camera_projection: None,
+			eye: view.inverse().w_axis.truncate(),
+			objects: HashMap::new(),
+			visible: Vec::new()
+		}
+	}
+
+	/// Create a new point of view from the given camera view matrix and projection.
+	///
+	/// The aspect ratio of the projection then follows the size of the render target.
+	pub fn with_projection<P: 'static + Projection>(view: Mat4, projection: P) -> Self {
+		let mut pov = Self::new(view, *projection.matrix());
+		pov.camera_projection = Some(Box::new(projection));
+		pov
+	}
+
+	/// View matrix (world to camera space).
+	pub fn view(&self) -> &Mat4 {
+		&self.view
+	}
+
+	/// Projection matrix (camera to clip space).
+	pub fn projection(&self) -> &Mat4 {
+		&self.projection
+	}
+
+	/// Camera position in world space.
+	pub fn eye(&self) -> Vec3 {
+		self.eye
+	}
+
+	/// Move the camera.
+	pub fn set_view(&mut self, view: Mat4) {
+		self.view = view;
+		self.eye = view.inverse().w_axis.truncate()
+	}
+
+	/// Move the camera to the current placement of the given camera.
+	pub fn follow<C: Camera>(&mut self, camera: &C) {
+		self.set_view(camera.view())
+	}
+
+	/// Change the camera projection matrix.
+	///
+	/// The projection is then fixed and does not follow the render target size anymore.
+	pub fn set_projection(&mut self, projection: Mat4) {
+		self.projection = projection;
+		self.camera_projection = None
+	}
+
+	/// Change the camera projection, following the render target size.
+	pub fn set_camera_projection<P: 'static + Projection>(&mut self, projection: P) {
+		self.projection = *projection.matrix();
+		self.camera_projection = Some(Box::new(projection))
+	}
+
+	/// Register an object, or update its bounding volume.
+	pub fn insert<V: Into<Volume>>(&mut self, object: Id<T>, volume: V) {
+		self.objects.insert(object, volume.into());
+	}
+
+	/// Unregister an object.
+	pub fn remove(&mut self, object: Id<T>) -> Option<Volume> {
+		self.objects.remove(&object)
+	}
+
+	/// Bounding volume of the given object, if registered.
+	pub fn volume(&self, object: Id<T>) -> Option<&Volume> {
+		self.objects.get(&object)
+	}
+
+	/// Camera frustum.
+	pub fn frustum(&self) -> Frustum {
+		Frustum::from_matrix(&(self.projection * self.view))
+	}
+}
+
+impl<T, E> PointOfView<T, E> for FrustumPointOfView<T> where Id<T>: Copy + Eq + Hash {
+	type Iter<'a> where T: 'a = std::slice::Iter<'a, Id<T>>;
+
+	fn cycle(&mut self, _scene: &Scene<T, E>) {
+		let frustum = self.frustum();
+		let objects = &self.objects;
+
+		self.visible.clear();
+		self.visible.extend(objects.iter().filter_map(|(id, volume)| {
+			if frustum.intersects(volume) {
+				Some(*id)
+			} else {
+				None
+			}
+		}))
+	}
+
+	fn visible_objects<'a>(&'a self) -> Self::Iter<'a> {
+		self.visible.iter()
+	}
+
+	fn distance(&self, object: &Id<T>) -> f32 {
+		match self.objects.get(object) {
+			Some(volume) => (volume.center() - self.eye).length(),
+			None => 0.0
+		}
+	}
+
+	fn volume(&self, object: &Id<T>) -> Option<Volume> {
+		self.objects.get(object).cloned()
+	}
+
+	fn resize(&mut self, width: u32, height: u32) {
+		if let Some(camera_projection) = &mut self.camera_projection {
+			camera_projection.resize(width, height);
+			self.projection = *camera_projection.matrix()
+		}
+	}
+}
@@ -0,0 +1,49 @@
+use scene::{
+	Scene,
+	Id
+};
+use crate::bounds::Volume;
+use super::OcclusionCulling;
+
+mod frustum;
+
+pub use frustum::FrustumPointOfView;
+
+pub trait PointOfView<T, E> {
+	type Iter<'a>: Iterator<Item=&'a Id<T>> where T: 'a;
+
+	fn cycle(&mut self, scene: &Scene<T, E>);
+
+	fn visible_objects<'a>(&'a self) -> Self::Iter<'a>;
+
+	/// Distance between the point of view and the given visible object.
+	///
+	/// Used to sort draws front to back (or back to front for blended objects).
+	/// By default, every object is considered at distance `0.0`.
+	fn distance(&self, _object: &Id<T>) -> f32 {
+		0.0
+	}
+
+	/// World-space bounding volume of the given visible object, if known.
+	///
+	/// Used by [`OcclusionPointOfView`](super::OcclusionPointOfView).
+	/// Objects without bounding volume are never culled.
+	fn volume(&self, _object: &Id<T>) -> Option<Volume> {
+		None
+	}
+
+	/// Adapt the point of view to a render target of the given size, in pixels.
+	///
+	/// Called by the render worker when its target is resized.
+	fn resize(&mut self, _width: u32, _height: u32) {
+		// nothing by default.
+	}
+
+	/// Occlusion culling stage of the point of view, if any.
+	///
+	/// The render worker copies the depth of the rendered frames back into it,
+	/// from targets supporting it.
+	fn occlusion_culling_mut(&mut self) -> Option<&mut OcclusionCulling<T>> {
+		None
+	}
+}
@@ -0,0 +1,61 @@
+//! Barriers and memory operations of host readbacks.
+use magma::{
+	command,
+	image,
+	mem::buffer,
+	pipeline
+};
+
+/// Record a barrier making the writes of an attachment available to transfer commands.
+///
+/// The image must already be in the given layout (the final layout of the render pass).
+pub(crate) fn record_attachment_barrier<B: command::Buffer>(
+	commands: &mut command::buffer::Recorder<B>,
+	image: &image::Bound,
+	layout: image::Layout,
+	aspect: image::Aspect
+) {
+	let (src_stage, src_access) = match aspect {
+		image::Aspect::Color => (pipeline::Stage::ColorAttachmentOutput, command::buffer::Access::ColorAttachmentWrite),
+		_ => (pipeline::Stage::LateFragmentTests, command::buffer::Access::DepthStencilAttachmentWrite)
+	};
+
+	commands.image_pipeline_barrier(
+		src_stage,
+		pipeline::Stage::Transfer,
+		&[command::buffer::ImageMemoryBarrier {
+			image: image.handle(),
+			old_layout: layout,
+			new_layout: layout,
+			aspect,
+			src_access,
+			dst_access: command::buffer::Access::TransferRead,
+			src_queue_family: command::buffer::QUEUE_FAMILY_IGNORED,
+			dst_queue_family: command::buffer::QUEUE_FAMILY_IGNORED
+		}]
+	)
+}
+
+/// Record a barrier making the transfer writes to the given buffer available to the host.
+pub(crate) fn record_host_barrier<B: command::Buffer>(commands: &mut command::buffer::Recorder<B>, buffer: &buffer::Bound, size: u64) {
+	commands.pipeline_barrier(
+		pipeline::Stage::Transfer,
+		pipeline::Stage::Host,
+		&[command::buffer::BufferMemoryBarrier {
+			buffer: buffer.handle(),
+			offset: 0,
+			size,
+			src_access: command::buffer::Access::TransferWrite,
+			dst_access: command::buffer::Access::HostRead,
+			src_queue_family: command::buffer::QUEUE_FAMILY_IGNORED,
+			dst_queue_family: command::buffer::QUEUE_FAMILY_IGNORED
+		}]
+	)
+}
+
+/// Make the device writes to the given host visible buffer visible to the host.
+///
+/// Required before reading non-coherent memory. Does nothing on coherent memory.
+pub(crate) fn invalidate(buffer: &buffer::Bound) {
+	buffer.invalidate_mapped_memory().expect("unable to invalidate readback memory")
+}
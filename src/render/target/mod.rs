@@ -0,0 +1,69 @@
+use std::{
+	sync::Arc,
+	ops::Deref
+};
+use magma::{
+	Device,
+	command,
+	framebuffer::RenderPass
+};
+
+mod offscreen;
+pub mod swapchain;
+
+pub use offscreen::OffscreenTarget;
+pub use swapchain::SwapchainTarget;
+
+pub trait Target {
+	fn device(&self) -> &Arc<Device>;
+
+	fn render_pass(&self) -> &Arc<RenderPass>;
+
+	/// Current size of the target, in pixels.
+	///
+	/// The render worker checks it at each cycle,
+	/// and notifies its point of view and resize listeners when it changes.
+	fn extent(&self) -> (u32, u32);
+
+	/// Record the copy of the depth image of the last render pass into host visible memory.
+	///
+	/// Used by the occlusion culling stage of the render worker.
+	/// Returns `false` if the target does not support depth readbacks, which is the default.
+	fn record_depth_readback<B: command::Buffer>(&self, _commands: &mut command::buffer::Recorder<B>) -> bool {
+		false
+	}
+
+	/// Depth copied by the last recorded depth readback, with its width and height.
+	///
+	/// Depth values are given row by row, starting from the top-left corner.
+	///
+	/// # Safety
+	///
+	/// The commands recorded by the last call to [`Target::record_depth_readback`]
+	/// must have been executed.
+	unsafe fn read_depth(&self) -> Option<(Vec<f32>, u32, u32)> {
+		None
+	}
+}
+
+impl<T: Deref> Target for T where T::Target: Target {
+	fn device(&self) -> &Arc<Device> {
+		Deref::deref(self).device()
+	}
+
+	fn render_pass(&self) -> &Arc<RenderPass> {
+		Deref::deref(self).render_pass()
+	}
+
+	fn extent(&self) -> (u32, u32) {
+		Deref::deref(self).extent()
+	}
+
+	fn record_depth_readback<B: command::Buffer>(&self, commands: &mut command::buffer::Recorder<B>) -> bool {
+		Deref::deref(self).record_depth_readback(commands)
+	}
+
+	unsafe fn read_depth(&self) -> Option<(Vec<f32>, u32, u32)> {
+		Deref::deref(self).read_depth()
+	}
+}
\ No newline at end of file
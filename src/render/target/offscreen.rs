@@ -0,0 +1,293 @@
+use std::sync::Arc;
+use magma::{
+	device,
+	Device,
+	DeviceOwned,
+	command::{
+		self,
+		Buffer as _
+	},
+	format::Format,
+	framebuffer::{
+		self,
+		RenderPass,
+		Framebuffer
+	},
+	image,
+	mem::{
+		Allocator,
+		buffer
+	},
+	sync::future::SignalFence
+};
+use crate::sync::{
+	FencePool,
+	CommandBufferPool
+};
+use super::{
+	Target,
+	super::{
+		memory::Memory,
+		readback
+	}
+};
+
+/// Color format of offscreen targets.
+pub const COLOR_FORMAT: Format = Format::R8G8B8A8Unorm;
+
+/// Depth format of offscreen targets.
+pub const DEPTH_FORMAT: Format = Format::D32Sfloat;
+
+/// Size of the readback of an image of the given extent, in bytes.
+///
+/// Color and depth texels both take 4 bytes.
+fn readback_len((width, height): (u32, u32)) -> usize {
+	(width * height * 4) as usize
+}
+
+/// Offscreen render target.
+///
+/// Owns its color and depth images, and does not require any surface,
+/// so it can be used with a headless device (for instance a software driver).
+/// The rendered color image can be copied back to the host as tightly packed RGBA8 pixels.
+pub struct OffscreenTarget {
+	queue: device::Queue,
+	memory: Box<dyn Memory>,
+	render_pass: Arc<RenderPass>,
+	extent: (u32, u32),
+	color: Arc<image::Bound>,
+	depth: Arc<image::Bound>,
+	framebuffer: Arc<Framebuffer>,
+	readback: Option<(buffer::Bound, *mut u8)>,
+	depth_readback: (buffer::Bound, *mut u8),
+	command_buffer_pool: CommandBufferPool,
+	fence_pool: FencePool
+}
+
+impl OffscreenTarget {
+	/// Create a new offscreen target of the given extent.
+	///
+	/// Images are allocated with the given allocator, which must also provide
+	/// host visible memory for readbacks.
+	/// The given queue is used to record and submit readbacks, and the images
+	/// are shared with it.
+	pub fn new<A: 'static + Allocator>(allocator: A, queue: device::Queue, extent: (u32, u32)) -> Self {
+		let device = queue.device().clone();
+		let mut memory: Box<dyn Memory> = Box::new(allocator);
+
+		let render_pass = Arc::new(RenderPass::new(
+			&device,
+			&[
+				framebuffer::render_pass::Attachment::new(
+					COLOR_FORMAT,
+					framebuffer::render_pass::LoadOp::Clear,
+					framebuffer::render_pass::StoreOp::Store,
+					image::Layout::Undefined,
+					image::Layout::TransferSrcOptimal
+				),
+				framebuffer::render_pass::Attachment::new(
+					DEPTH_FORMAT,
+					framebuffer::render_pass::LoadOp::Clear,
+					framebuffer::render_pass::StoreOp::Store,
+					image::Layout::Undefined,
+					image::Layout::TransferSrcOptimal
+				)
+			],
+			&[framebuffer::render_pass::Subpass::new(&[0], Some(1))]
+		).expect("unable to create offscreen render pass"));
+
+		let (color, depth, framebuffer) = Self::attachments(&device, memory.as_mut(), &render_pass, &queue, extent);
+		let depth_readback = Self::new_readback_buffer(memory.as_mut(), &queue, extent);
+		let command_buffer_pool = CommandBufferPool::new(&queue).expect("unable to create command buffer pool");
+		let fence_pool = FencePool::new(&device);
+
+		Self {
+			queue,
+			memory,
+			render_pass,
+			extent,
+			color,
+			depth,
+			framebuffer,
+			readback: None,
+			depth_readback,
+			command_buffer_pool,
+			fence_pool
+		}
+	}
+
+	fn attachments(
+		device: &Arc<Device>,
+		memory: &mut dyn Memory,
+		render_pass: &Arc<RenderPass>,
+		queue: &device::Queue,
+		extent: (u32, u32)
+	) -> (Arc<image::Bound>, Arc<image::Bound>, Arc<Framebuffer>) {
+		let color = Arc::new(memory.bind_image(image::Unbound::new(
+			device,
+			image::Type::D2,
+			COLOR_FORMAT,
+			extent,
+			image::Usage::ColorAttachment | image::Usage::TransferSource,
+			queue
+		).expect("unable to create color image")));
+
+		let depth = Arc::new(memory.bind_image(image::Unbound::new(
+			device,
+			image::Type::D2,
+			DEPTH_FORMAT,
+			extent,
+			image::Usage::DepthStencilAttachment | image::Usage::TransferSource,
+			queue
+		).expect("unable to create depth image")));
+
+		let framebuffer = Arc::new(Framebuffer::new(
+			render_pass,
+			vec![color.view(), depth.view()],
+			extent
+		).expect("unable to create offscreen framebuffer"));
+
+		(color, depth, framebuffer)
+	}
+
+	/// Resize the target.
+	///
+	/// Images and framebuffer are recreated, and the previous readback is discarded.
+	/// Commands using the previous framebuffer must have been executed.
+	pub fn resize(&mut self, extent: (u32, u32)) {
+		if self.extent != extent {
+			let device = self.queue.device().clone();
+			let (color, depth, framebuffer) = Self::attachments(&device, self.memory.as_mut(), &self.render_pass, &self.queue, extent);
+			self.extent = extent;
+			self.color = color;
+			self.depth = depth;
+			self.framebuffer = framebuffer;
+			self.readback = None;
+			self.depth_readback = Self::new_readback_buffer(self.memory.as_mut(), &self.queue, extent)
+		}
+	}
+
+	/// Framebuffer to render into.
+	pub fn framebuffer(&self) -> &Arc<Framebuffer> {
+		&self.framebuffer
+	}
+
+	/// Color image.
+	///
+	/// After the render pass, the image is in the `TransferSrcOptimal` layout.
+	pub fn color_image(&self) -> &Arc<image::Bound> {
+		&self.color
+	}
+
+	/// Depth image.
+	///
+	/// After the render pass, the image is in the `TransferSrcOptimal` layout.
+	pub fn depth_image(&self) -> &Arc<image::Bound> {
+		&self.depth
+	}
+
+	/// Size of the readback, in bytes.
+	fn readback_len(&self) -> usize {
+		readback_len(self.extent)
+	}
+
+	/// Host visible buffer to copy an image of the given extent into.
+	fn new_readback_buffer(memory: &mut dyn Memory, queue: &device::Queue, extent: (u32, u32)) -> (buffer::Bound, *mut u8) {
+		let buffer = buffer::Unbound::new(
+			queue.device(),
+			readback_len(extent) as u64,
+			buffer::Usage::TransferDestination,
+			queue
+		).expect("unable to create readback buffer");
+
+		memory.bind_host_visible_buffer(buffer)
+	}
+
+	/// Record the copy of the given image into the given readback buffer.
+	fn record_copy<B: command::Buffer>(&self, commands: &mut command::buffer::Recorder<B>, image: &image::Bound, aspect: image::Aspect, buffer: &buffer::Bound) {
+		readback::record_attachment_barrier(commands, image, image::Layout::TransferSrcOptimal, aspect);
+		commands.copy_image_to_buffer(image, image::Layout::TransferSrcOptimal, buffer, &[command::buffer::BufferImageCopy {
+			buffer_offset: 0,
+			buffer_row_length: 0,
+			buffer_image_height: 0,
+			image_offset: (0, 0, 0),
+			image_extent: (self.extent.0, self.extent.1, 1)
+		}]);
+		readback::record_host_barrier(commands, buffer, self.readback_len() as u64);
+	}
+
+	/// Record the copy of the color image into the readback buffer.
+	///
+	/// Must be recorded after the render pass.
+	/// The pixels can then be read with [`OffscreenTarget::read`] once the commands have been executed.
+	pub fn record_readback<B: command::Buffer>(&mut self, commands: &mut command::buffer::Recorder<B>) {
+		if self.readback.is_none() {
+			self.readback = Some(Self::new_readback_buffer(self.memory.as_mut(), &self.queue, self.extent))
+		}
+
+		let (buffer, _) = self.readback.as_ref().unwrap();
+		self.record_copy(commands, &self.color, image::Aspect::Color, buffer)
+	}
+
+	/// Read the pixels copied by the last recorded readback.
+	///
+	/// Pixels are tightly packed RGBA8 values, row by row starting from the top.
+	/// Returns `None` if no readback has been recorded.
+	///
+	/// # Safety
+	///
+	/// The commands recorded by the last call to [`OffscreenTarget::record_readback`]
+	/// must have been executed.
+	pub unsafe fn read(&self) -> Option<Vec<u8>> {
+		self.readback.as_ref().map(|(buffer, ptr)| {
+			readback::invalidate(buffer);
+			std::slice::from_raw_parts(*ptr, self.readback_len()).to_vec()
+		})
+	}
+
+	/// Copy the color image back to the host, and wait for it.
+	///
+	/// Every command rendering to this target must have been submitted to the target queue before.
+	pub fn read_rgba8(&mut self) -> Vec<u8> {
+		let command_buffer = self.command_buffer_pool.get().expect("unable to allocate command buffer");
+		let recorded_command_buffer = command_buffer.record(|commands| {
+			self.record_readback(commands)
+		}).expect("unable to record command buffer");
+
+		let fence = self.fence_pool.get().expect("unable to create fence");
+		let (_, future) = self.queue.submit(recorded_command_buffer).then_signal_fence(fence).expect("unable to submit command buffer");
+		future.wait(None).expect("fence error");
+
+		unsafe {
+			// Safe because the readback has been executed.
+			self.read().unwrap()
+		}
+	}
+}
+
+impl Target for OffscreenTarget {
+	fn device(&self) -> &Arc<Device> {
+		self.queue.device()
+	}
+
+	fn render_pass(&self) -> &Arc<RenderPass> {
+		&self.render_pass
+	}
+
+	fn extent(&self) -> (u32, u32) {
+		self.extent
+	}
+
+	fn record_depth_readback<B: command::Buffer>(&self, commands: &mut command::buffer::Recorder<B>) -> bool {
+		let (buffer, _) = &self.depth_readback;
+		self.record_copy(commands, &self.depth, image::Aspect::Depth, buffer);
+		true
+	}
+
+	unsafe fn read_depth(&self) -> Option<(Vec<f32>, u32, u32)> {
+		let (buffer, ptr) = &self.depth_readback;
+		readback::invalidate(buffer);
+		let depths = std::slice::from_raw_parts(*ptr as *const f32, (self.extent.0 * self.extent.1) as usize).to_vec();
+		Some((depths, self.extent.0, self.extent.1))
+	}
+}
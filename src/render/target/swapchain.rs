@@ -0,0 +1,334 @@
+use std::sync::Arc;
+use magma::{
+	device,
+	Device,
+	DeviceOwned,
+	format::Format,
+	framebuffer::{
+		self,
+		RenderPass,
+		Framebuffer
+	},
+	image,
+	mem::Allocator,
+	swapchain::{
+		self,
+		Surface,
+		Swapchain,
+		ColorSpace,
+		PresentMode,
+		CompositeAlpha
+	},
+	sync::Semaphore
+};
+use super::{
+	Target,
+	super::memory::Memory
+};
+
+/// Depth format of swapchain targets.
+pub const DEPTH_FORMAT: Format = Format::D32Sfloat;
+
+/// Swapchain render target.
+///
+/// Renders into the images of a surface swapchain.
+/// The swapchain is recreated when the target is resized,
+/// or when it becomes out of date or suboptimal.
+pub struct SwapchainTarget {
+	queue: device::Queue,
+	surface: Arc<Surface>,
+	memory: Box<dyn Memory>,
+	render_pass: Arc<RenderPass>,
+	color_format: Format,
+	color_space: ColorSpace,
+	present_mode: PresentMode,
+	extent: (u32, u32),
+	swapchain: Option<Images>,
+
+	/// Image acquisition semaphores, used in turn.
+	acquisition_semaphores: Vec<Arc<Semaphore>>,
+	next_semaphore: usize
+}
+
+/// Swapchain and its images.
+struct Images {
+	handle: Swapchain,
+
+	/// Requested extent, when the swapchain was created.
+	requested_extent: (u32, u32),
+
+	/// Actual extent, clamped to the surface capabilities.
+	extent: (u32, u32),
+	framebuffers: Vec<Arc<Framebuffer>>,
+
+	/// Signaled when the rendering of each image is done.
+	rendered: Vec<Arc<Semaphore>>,
+	_depth: Arc<image::Bound>,
+
+	/// If `false`, the swapchain should be recreated before the next acquisition.
+	optimal: bool
+}
+
+/// Acquired swapchain image.
+pub struct AcquiredImage {
+	index: u32,
+	framebuffer: Arc<Framebuffer>,
+	acquired: Arc<Semaphore>,
+	rendered: Arc<Semaphore>
+}
+
+impl AcquiredImage {
+	/// Index of the image in the swapchain.
+	pub fn index(&self) -> u32 {
+		self.index
+	}
+
+	/// Framebuffer to render into.
+	pub fn framebuffer(&self) -> &Arc<Framebuffer> {
+		&self.framebuffer
+	}
+
+	/// Semaphore signaled when the image is acquired.
+	///
+	/// Rendering commands must wait for it.
+	pub fn acquired(&self) -> &Arc<Semaphore> {
+		&self.acquired
+	}
+
+	/// Semaphore to signal when the rendering is done.
+	///
+	/// The presentation waits for it.
+	pub fn rendered(&self) -> &Arc<Semaphore> {
+		&self.rendered
+	}
+}
+
+impl SwapchainTarget {
+	/// Create a new swapchain target for the given surface.
+	///
+	/// The given queue must support presentation to the surface.
+	/// The first supported present mode of `present_modes` is selected,
+	/// falling back to `PresentMode::Fifo` which is always supported.
+	/// Depth images are allocated with the given allocator.
+	pub fn new<A: 'static + Allocator>(
+		allocator: A,
+		queue: device::Queue,
+		surface: Arc<Surface>,
+		extent: (u32, u32),
+		present_modes: &[PresentMode]
+	) -> Self {
+		let device = queue.device().clone();
+		let capabilities = surface.capabilities(device.physical_device()).expect("unable to get surface capabilities");
+
+		let (color_format, color_space) = choose_format(&capabilities).expect("no appropriate surface format found");
+		let present_mode = present_modes.iter().copied().find(|mode| {
+			capabilities.present_modes.contains(mode)
+		}).unwrap_or(PresentMode::Fifo);
+
+		let render_pass = RenderPass::new(
+			&device,
+			&[
+				framebuffer::render_pass::Attachment::new(
+					color_format,
+					framebuffer::render_pass::LoadOp::Clear,
+					framebuffer::render_pass::StoreOp::Store,
+					image::Layout::Undefined,
+					image::Layout::PresentSrc
+				),
+				framebuffer::render_pass::Attachment::new(
+					DEPTH_FORMAT,
+					framebuffer::render_pass::LoadOp::Clear,
+					framebuffer::render_pass::StoreOp::DontCare,
+					image::Layout::Undefined,
+					image::Layout::DepthStencilAttachmentOptimal
+				)
+			],
+			&[framebuffer::render_pass::Subpass::new(&[0], Some(1))]
+		).expect("unable to create swapchain render pass");
+
+		Self {
+			queue,
+			surface,
+			memory: Box::new(allocator),
+			render_pass: Arc::new(render_pass),
+			color_format,
+			color_space,
+			present_mode,
+			extent,
+			swapchain: None,
+			acquisition_semaphores: Vec::new(),
+			next_semaphore: 0
+		}
+	}
+
+	/// Selected present mode.
+	pub fn present_mode(&self) -> PresentMode {
+		self.present_mode
+	}
+
+	/// Resize the target, typically when the window is resized.
+	///
+	/// The swapchain is recreated at the next acquisition.
+	pub fn resize(&mut self, extent: (u32, u32)) {
+		self.extent = extent
+	}
+
+	fn is_compatible(&self) -> bool {
+		match &self.swapchain {
+			Some(swapchain) => swapchain.optimal && swapchain.requested_extent == self.extent,
+			None => false
+		}
+	}
+
+	/// Checks if the swapchain will be recreated at the next acquisition.
+	///
+	/// Recreating the swapchain destroys its images, framebuffers and semaphores:
+	/// every frame in flight must be complete before (see [`Frames::wait_idle`](crate::render::Frames::wait_idle)).
+	pub fn needs_rebuild(&self) -> bool {
+		self.extent.0 > 0 && self.extent.1 > 0 && self.swapchain.is_some() && !self.is_compatible()
+	}
+
+	/// (Re)create the swapchain and its images.
+	fn rebuild(&mut self) {
+		let device = self.queue.device().clone();
+		let capabilities = self.surface.capabilities(device.physical_device()).expect("unable to get surface capabilities");
+		let extent = capabilities.clamp_extent(self.extent);
+
+		let old_swapchain = self.swapchain.take().map(|swapchain| swapchain.handle);
+		let handle = Swapchain::new(
+			&device,
+			&self.surface,
+			old_swapchain,
+			capabilities.min_image_count + 1,
+			self.color_format,
+			self.color_space,
+			extent,
+			image::Usage::ColorAttachment,
+			&self.queue,
+			capabilities.current_transform,
+			CompositeAlpha::Opaque, // ignore alpha component.
+			self.present_mode,
+			true
+		).expect("unable to create swapchain");
+
+		let depth = Arc::new(self.memory.bind_image(image::Unbound::new(
+			&device,
+			image::Type::D2,
+			DEPTH_FORMAT,
+			extent,
+			image::Usage::DepthStencilAttachment,
+			&self.queue
+		).expect("unable to create depth image")));
+
+		let framebuffers: Vec<_> = handle.images().iter().map(|image| {
+			Arc::new(Framebuffer::new(
+				&self.render_pass,
+				vec![image.view(), depth.view()],
+				extent
+			).expect("unable to create swapchain framebuffer"))
+		}).collect();
+
+		let rendered = framebuffers.iter().map(|_| {
+			Arc::new(Semaphore::new(&device).expect("unable to create semaphore"))
+		}).collect();
+
+		// One more semaphore than images, so that a semaphore is never reused while its acquisition is pending.
+		while self.acquisition_semaphores.len() <= framebuffers.len() {
+			self.acquisition_semaphores.push(Arc::new(Semaphore::new(&device).expect("unable to create semaphore")))
+		}
+
+		self.swapchain = Some(Images {
+			handle,
+			requested_extent: self.extent,
+			extent,
+			framebuffers,
+			rendered,
+			_depth: depth,
+			optimal: true
+		})
+	}
+
+	/// Acquire the next swapchain image.
+	///
+	/// The swapchain is recreated first if needed,
+	/// in which case every frame in flight must be complete (see [`SwapchainTarget::needs_rebuild`]).
+	/// Returns `None` if the target has a null extent (for instance a minimized window),
+	/// or if the swapchain is out of date. It is then recreated at the next acquisition.
+	pub fn acquire(&mut self) -> Option<AcquiredImage> {
+		if self.extent.0 == 0 || self.extent.1 == 0 {
+			return None
+		}
+
+		if !self.is_compatible() {
+			self.rebuild()
+		}
+
+		let acquired = self.acquisition_semaphores[self.next_semaphore].clone();
+		let swapchain = self.swapchain.as_mut().unwrap();
+
+		match swapchain.handle.acquire_next_image(None, Some(&acquired), None) {
+			Ok((index, suboptimal)) => {
+				if suboptimal {
+					swapchain.optimal = false
+				}
+
+				self.next_semaphore = (self.next_semaphore + 1) % self.acquisition_semaphores.len();
+
+				Some(AcquiredImage {
+					index,
+					framebuffer: swapchain.framebuffers[index as usize].clone(),
+					acquired,
+					rendered: swapchain.rendered[index as usize].clone()
+				})
+			},
+			Err(swapchain::AcquireError::OutOfDate) => {
+				swapchain.optimal = false;
+				None
+			},
+			Err(e) => panic!("unable to acquire swapchain image: {:?}", e)
+		}
+	}
+
+	/// Present the given image, once its rendering is done.
+	///
+	/// An out of date or suboptimal swapchain is recreated at the next acquisition.
+	pub fn present(&mut self, image: AcquiredImage) {
+		if let Some(swapchain) = &mut self.swapchain {
+			match self.queue.present(&swapchain.handle, image.index, &[&image.rendered]) {
+				Ok(suboptimal) => {
+					if suboptimal {
+						swapchain.optimal = false
+					}
+				},
+				Err(swapchain::PresentError::OutOfDate) => {
+					swapchain.optimal = false
+				},
+				Err(e) => panic!("unable to present swapchain image: {:?}", e)
+			}
+		}
+	}
+}
+
+impl Target for SwapchainTarget {
+	fn device(&self) -> &Arc<Device> {
+		self.queue.device()
+	}
+
+	fn render_pass(&self) -> &Arc<RenderPass> {
+		&self.render_pass
+	}
+
+	fn extent(&self) -> (u32, u32) {
+		match &self.swapchain {
+			Some(swapchain) => swapchain.extent,
+			None => self.extent
+		}
+	}
+}
+
+/// Choose a surface format and color space.
+fn choose_format(capabilities: &swapchain::Capabilities) -> Option<(Format, ColorSpace)> {
+	capabilities.supported_formats.iter().copied().find(|(format, color_space)| {
+		*format == Format::B8G8R8A8Srgb && *color_space == ColorSpace::SrgbNonLinear
+	}).or_else(|| capabilities.supported_formats.first().copied())
+}
@@ -0,0 +1,426 @@
+use std::{
+	hash::Hash,
+	cmp::Ordering,
+	collections::{
+		HashMap,
+		BinaryHeap,
+		hash_map
+	}
+};
+use glam::Vec3;
+use scene::Id;
+use crate::bounds::{
+	Aabb,
+	Sphere,
+	Frustum,
+	Ray
+};
+use super::Space;
+
+/// Default margin added around the objects bounding boxes.
+pub const DEFAULT_MARGIN: f32 = 0.1;
+
+/// Dynamic spatial index.
+///
+/// Bounding volume hierarchy of axis-aligned boxes, keyed by object id.
+/// Each object box is stored with a margin so that small movements do not
+/// require to restructure the tree.
+pub struct Index<T> {
+	nodes: Vec<Node<T>>,
+	free: Vec<usize>,
+	root: Option<usize>,
+	leaves: HashMap<Id<T>, usize>,
+	margin: f32
+}
+
+struct Node<T> {
+	/// Bounding box, including the margin for leaves.
+	aabb: Aabb,
+	parent: Option<usize>,
+	content: Content<T>
+}
+
+enum Content<T> {
+	/// Object with its exact bounding box.
+	Leaf(Id<T>, Aabb),
+	Branch(usize, usize),
+	Free
+}
+
+impl<T> Index<T> where Id<T>: Copy + Eq + Hash {
+	pub fn new() -> Self {
+		Self::with_margin(DEFAULT_MARGIN)
+	}
+
+	/// Create a new index with the given margin around the objects bounding boxes.
+	///
+	/// A greater margin makes updates cheaper for moving objects,
+	/// but queries less precise.
+	pub fn with_margin(margin: f32) -> Self {
+		Self {
+			nodes: Vec::new(),
+			free: Vec::new(),
+			root: None,
+			leaves: HashMap::new(),
+			margin
+		}
+	}
+
+	/// Number of objects in the index.
+	pub fn len(&self) -> usize {
+		self.leaves.len()
+	}
+
+	/// Checks if the index is empty.
+	pub fn is_empty(&self) -> bool {
+		self.leaves.is_empty()
+	}
+
+	/// Checks if the given object is in the index.
+	pub fn contains(&self, object: Id<T>) -> bool {
+		self.leaves.contains_key(&object)
+	}
+
+	/// Bounding box of the given object, if it is in the index.
+	pub fn get(&self, object: Id<T>) -> Option<&Aabb> {
+		self.leaves.get(&object).map(|leaf| match &self.nodes[*leaf].content {
+			Content::Leaf(_, aabb) => aabb,
+			_ => unreachable!()
+		})
+	}
+
+	/// Remove every object.
+	pub fn clear(&mut self) {
+		self.nodes.clear();
+		self.free.clear();
+		self.leaves.clear();
+		self.root = None
+	}
+
+	/// Insert an object with the given world-space bounding box.
+	///
+	/// If the object is already in the index, it is updated.
+	pub fn insert(&mut self, object: Id<T>, aabb: Aabb) {
+		self.update(object, aabb);
+	}
+
+	/// Update the bounding box of an object, typically after its transformation changed.
+	///
+	/// If the object is not in the index, it is inserted.
+	/// Returns `true` if the tree has been restructured,
+	/// or `false` if the new box fits in the margin of the previous one.
+	pub fn update(&mut self, object: Id<T>, aabb: Aabb) -> bool {
+		match self.leaves.get(&object).cloned() {
+			Some(leaf) => {
+				if self.nodes[leaf].aabb.contains_aabb(&aabb) {
+					self.nodes[leaf].content = Content::Leaf(object, aabb);
+					false
+				} else {
+					self.remove_leaf(leaf);
+					self.nodes[leaf].aabb = aabb.grown(self.margin);
+					self.nodes[leaf].content = Content::Leaf(object, aabb);
+					self.insert_leaf(leaf);
+					true
+				}
+			},
+			None => {
+				let leaf = self.allocate(Node {
+					aabb: aabb.grown(self.margin),
+					parent: None,
+					content: Content::Leaf(object, aabb)
+				});
+				self.insert_leaf(leaf);
+				self.leaves.insert(object, leaf);
+				true
+			}
+		}
+	}
+
+	/// Remove an object.
+	///
+	/// Returns its bounding box if it was in the index.
+	pub fn remove(&mut self, object: Id<T>) -> Option<Aabb> {
+		let leaf = self.leaves.remove(&object)?;
+		self.remove_leaf(leaf);
+		match std::mem::replace(&mut self.nodes[leaf].content, Content::Free) {
+			Content::Leaf(_, aabb) => {
+				self.free.push(leaf);
+				Some(aabb)
+			},
+			_ => unreachable!()
+		}
+	}
+
+	/// Visit every object whose bounding box intersects the given box.
+	pub fn query_aabb<F: FnMut(Id<T>, &Aabb)>(&self, aabb: &Aabb, f: F) {
+		self.traverse(|node| node.intersects_aabb(aabb), f)
+	}
+
+	/// Visit every object whose bounding box intersects the given frustum.
+	pub fn query_frustum<F: FnMut(Id<T>, &Aabb)>(&self, frustum: &Frustum, f: F) {
+		self.traverse(|node| frustum.intersects_aabb(node), f)
+	}
+
+	/// Visit every object whose bounding box intersects the given sphere.
+	pub fn query_sphere<F: FnMut(Id<T>, &Aabb)>(&self, sphere: &Sphere, f: F) {
+		let radius_squared = sphere.radius * sphere.radius;
+		self.traverse(|node| node.distance_squared(sphere.center) <= radius_squared, f)
+	}
+
+	/// Visit every object whose bounding box is hit by the given ray,
+	/// closer than `max_distance`.
+	///
+	/// The callback is given the distance along the ray where it enters the object box.
+	/// Objects are not visited in any particular order.
+	pub fn query_ray<F: FnMut(Id<T>, f32)>(&self, ray: &Ray, max_distance: f32, mut f: F) {
+		self.traverse(
+			|node| ray.intersect_aabb(node).map(|d| d <= max_distance).unwrap_or(false),
+			|object, aabb| if let Some(d) = ray.intersect_aabb(aabb) {
+				f(object, d)
+			}
+		)
+	}
+
+	/// Find the `k` objects nearest to the given point.
+	///
+	/// Returns the objects with the distance between their bounding box and the point,
+	/// ordered from the nearest to the farthest.
+	pub fn nearest(&self, point: Vec3, k: usize) -> Vec<(Id<T>, f32)> {
+		let mut result = Vec::with_capacity(k);
+		let mut heap = BinaryHeap::new();
+
+		if k > 0 {
+			if let Some(root) = self.root {
+				heap.push(self.candidate(root, point))
+			}
+		}
+
+		while let Some(Candidate { distance_squared, node }) = heap.pop() {
+			match &self.nodes[node].content {
+				Content::Leaf(object, _) => {
+					result.push((*object, distance_squared.sqrt()));
+					if result.len() == k {
+						break
+					}
+				},
+				Content::Branch(left, right) => {
+					heap.push(self.candidate(*left, point));
+					heap.push(self.candidate(*right, point))
+				},
+				Content::Free => unreachable!()
+			}
+		}
+
+		result
+	}
+
+	fn candidate(&self, node: usize, point: Vec3) -> Candidate {
+		let distance_squared = match &self.nodes[node].content {
+			Content::Leaf(_, aabb) => aabb.distance_squared(point),
+			_ => self.nodes[node].aabb.distance_squared(point)
+		};
+
+		Candidate {
+			distance_squared,
+			node
+		}
+	}
+
+	/// Visit every leaf whose ancestors and exact bounding box pass the given test.
+	fn traverse<F: FnMut(&Aabb) -> bool, G: FnMut(Id<T>, &Aabb)>(&self, mut test: F, mut visit: G) {
+		let mut stack = Vec::new();
+		stack.extend(self.root);
+
+		while let Some(index) = stack.pop() {
+			let node = &self.nodes[index];
+			if test(&node.aabb) {
+				match &node.content {
+					Content::Leaf(object, aabb) => {
+						if test(aabb) {
+							visit(*object, aabb)
+						}
+					},
+					Content::Branch(left, right) => {
+						stack.push(*left);
+						stack.push(*right)
+					},
+					Content::Free => unreachable!()
+				}
+			}
+		}
+	}
+
+	fn allocate(&mut self, node: Node<T>) -> usize {
+		match self.free.pop() {
+			Some(index) => {
+				self.nodes[index] = node;
+				index
+			},
+			None => {
+				self.nodes.push(node);
+				self.nodes.len() - 1
+			}
+		}
+	}
+
+	fn deallocate(&mut self, index: usize) {
+		self.nodes[index].content = Content::Free;
+		self.free.push(index)
+	}
+
+	/// Cost of inserting a box with the given bounds in the given sub-tree.
+	fn descent_cost(&self, index: usize, aabb: &Aabb) -> f32 {
+		let node = &self.nodes[index];
+		let union_area = node.aabb.union(aabb).surface_area();
+		match node.content {
+			Content::Leaf(_, _) => union_area,
+			_ => union_area - node.aabb.surface_area()
+		}
+	}
+
+	/// Insert a detached leaf in the tree.
+	fn insert_leaf(&mut self, leaf: usize) {
+		let root = match self.root {
+			Some(root) => root,
+			None => {
+				self.nodes[leaf].parent = None;
+				self.root = Some(leaf);
+				return
+			}
+		};
+
+		let aabb = self.nodes[leaf].aabb;
+
+		// Find the best sibling using the surface area heuristic.
+		let mut index = root;
+		while let Content::Branch(left, right) = self.nodes[index].content {
+			let area = self.nodes[index].aabb.surface_area();
+			let combined_area = self.nodes[index].aabb.union(&aabb).surface_area();
+
+			// Cost of creating a new parent for this node and the leaf.
+			let cost = 2.0 * combined_area;
+
+			// Minimum cost of pushing the leaf further down.
+			let inheritance_cost = 2.0 * (combined_area - area);
+
+			let left_cost = self.descent_cost(left, &aabb) + inheritance_cost;
+			let right_cost = self.descent_cost(right, &aabb) + inheritance_cost;
+
+			if cost < left_cost && cost < right_cost {
+				break
+			}
+
+			index = if left_cost < right_cost { left } else { right };
+		}
+
+		// Create a new parent.
+		let sibling = index;
+		let old_parent = self.nodes[sibling].parent;
+		let new_parent = self.allocate(Node {
+			aabb: aabb.union(&self.nodes[sibling].aabb),
+			parent: old_parent,
+			content: Content::Branch(sibling, leaf)
+		});
+
+		self.nodes[sibling].parent = Some(new_parent);
+		self.nodes[leaf].parent = Some(new_parent);
+
+		match old_parent {
+			Some(old_parent) => {
+				self.replace_child(old_parent, sibling, new_parent);
+				self.refit(Some(old_parent))
+			},
+			None => self.root = Some(new_parent)
+		}
+	}
+
+	/// Detach a leaf from the tree.
+	fn remove_leaf(&mut self, leaf: usize) {
+		if self.root == Some(leaf) {
+			self.root = None;
+			return
+		}
+
+		let parent = self.nodes[leaf].parent.unwrap();
+		let sibling = match self.nodes[parent].content {
+			Content::Branch(left, right) => if left == leaf { right } else { left },
+			_ => unreachable!()
+		};
+
+		let grand_parent = self.nodes[parent].parent;
+		self.nodes[sibling].parent = grand_parent;
+
+		match grand_parent {
+			Some(grand_parent) => {
+				self.replace_child(grand_parent, parent, sibling);
+				self.refit(Some(grand_parent))
+			},
+			None => self.root = Some(sibling)
+		}
+
+		self.nodes[leaf].parent = None;
+		self.deallocate(parent)
+	}
+
+	fn replace_child(&mut self, parent: usize, old_child: usize, new_child: usize) {
+		if let Content::Branch(left, right) = &mut self.nodes[parent].content {
+			if *left == old_child {
+				*left = new_child
+			} else {
+				*right = new_child
+			}
+		}
+	}
+
+	/// Recompute the bounding boxes of the given node and its ancestors.
+	fn refit(&mut self, mut index: Option<usize>) {
+		while let Some(i) = index {
+			if let Content::Branch(left, right) = self.nodes[i].content {
+				self.nodes[i].aabb = self.nodes[left].aabb.union(&self.nodes[right].aabb)
+			}
+
+			index = self.nodes[i].parent
+		}
+	}
+}
+
+impl<T> Default for Index<T> where Id<T>: Copy + Eq + Hash {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<T> Space<T> for Index<T> {
+	type Iter<'a> where T: 'a = hash_map::Keys<'a, Id<T>, usize>;
+
+	fn objects<'a>(&'a self) -> Self::Iter<'a> {
+		self.leaves.keys()
+	}
+}
+
+/// Nearest-neighbour search candidate.
+struct Candidate {
+	distance_squared: f32,
+	node: usize
+}
+
+impl PartialEq for Candidate {
+	fn eq(&self, other: &Self) -> bool {
+		self.cmp(other) == Ordering::Equal
+	}
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+impl Ord for Candidate {
+	fn cmp(&self, other: &Self) -> Ordering {
+		// Reversed, so that the binary heap pops the nearest candidate first.
+		other.distance_squared.partial_cmp(&self.distance_squared).unwrap_or(Ordering::Equal)
+	}
+}
@@ -1,24 +1,136 @@
+use std::{
+	ops::Div,
+	hash::Hash,
+	collections::HashMap
+};
+use scene::{
+	Scene,
+	Id
+};
 use crate::render;
 
+pub mod index;
+
+pub use index::Index;
+
+/// Distance between two objects, if comparable.
+///
+/// Objects may not be comparable, for instance if they are not in the same reference frame.
 pub trait PartialDistance<D> {
 	fn distance(&self, other: &Self) -> Option<D>;
 }
 
+/// Object with a scale (typically its size).
 pub trait Scaled<D> {
 	fn scale(&self) -> D;
 }
 
-pub struct Filter<S> {
-	space: S
+/// Collection of objects located in space.
+pub trait Space<T> {
+	type Iter<'a>: Iterator<Item=&'a Id<T>> where Self: 'a, T: 'a;
+
+	fn objects<'a>(&'a self) -> Self::Iter<'a>;
+}
+
+impl<T> Space<T> for Vec<Id<T>> {
+	type Iter<'a> where T: 'a = std::slice::Iter<'a, Id<T>>;
+
+	fn objects<'a>(&'a self) -> Self::Iter<'a> {
+		self.iter()
+	}
+}
+
+/// Distance/scale-based point of view.
+///
+/// Keeps only the objects of the space that matter from the observer position:
+/// an object is visible if its scale divided by its distance to the observer
+/// is greater or equal to the threshold.
+/// Objects whose distance to the observer is unknown are dropped,
+/// and objects at a null distance (such as the observer itself) are always visible.
+pub struct Filter<S, T, D> {
+	/// Candidate objects.
+	space: S,
+
+	/// Observer object.
+	observer: Id<T>,
+
+	/// Minimum scale/distance ratio.
+	threshold: D,
+
+	/// Visible objects, computed during the last cycle.
+	visible: Vec<Id<T>>,
+
+	/// Distance of the visible objects to the observer.
+	distances: HashMap<Id<T>, f32>
+}
+
+impl<S: Space<T>, T, D> Filter<S, T, D> {
+	pub fn new(space: S, observer: Id<T>, threshold: D) -> Self {
+		Self {
+			space,
+			observer,
+			threshold,
+			visible: Vec::new(),
+			distances: HashMap::new()
+		}
+	}
+
+	/// Candidate objects.
+	pub fn space(&self) -> &S {
+		&self.space
+	}
+
+	/// Candidate objects.
+	pub fn space_mut(&mut self) -> &mut S {
+		&mut self.space
+	}
+
+	/// Observer object.
+	pub fn observer(&self) -> &Id<T> {
+		&self.observer
+	}
+
+	/// Change the observer object.
+	pub fn set_observer(&mut self, observer: Id<T>) {
+		self.observer = observer
+	}
+
+	/// Minimum scale/distance ratio.
+	pub fn threshold(&self) -> &D {
+		&self.threshold
+	}
+
+	/// Change the minimum scale/distance ratio.
+	pub fn set_threshold(&mut self, threshold: D) {
+		self.threshold = threshold
+	}
 }
 
-impl<E, S: Scene<T, E>> render::Scene for Filter<S> where  {
-	type Object: Sync = Self::Item;
-	type Event: Sync;
+impl<S: Space<T>, T: PartialDistance<D> + Scaled<D>, E, D: Copy + Default + PartialOrd + Div<Output=D> + Into<f64>> render::PointOfView<T, E> for Filter<S, T, D> where Id<T>: Copy + Eq + Hash {
+	type Iter<'a> where T: 'a = std::slice::Iter<'a, Id<T>>;
 
-	type Iter<'a>: Iterator<Item=&'a Id<Self::Object>> where Self::Object: 'a;
+	fn cycle(&mut self, scene: &Scene<T, E>) {
+		let observer = scene.get(&self.observer);
+		let threshold = self.threshold;
 
-	fn relevant_objects<'a>(&'a self) -> Self::Iter<'a>;
+		self.visible.clear();
+		self.distances.clear();
+		for id in self.space.objects() {
+			let object = scene.get(id);
+			if let Some(distance) = object.distance(&observer) {
+				if distance <= D::default() || object.scale() / distance >= threshold {
+					self.visible.push(*id);
+					self.distances.insert(*id, distance.into() as f32);
+				}
+			}
+		}
+	}
 
-	fn view(&self, object: &Self::Object) -> View;
-}
\ No newline at end of file
+	fn visible_objects<'a>(&'a self) -> Self::Iter<'a> {
+		self.visible.iter()
+	}
+
+	fn distance(&self, object: &Id<T>) -> f32 {
+		self.distances.get(object).cloned().unwrap_or(0.0)
+	}
+}
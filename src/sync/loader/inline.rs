@@ -0,0 +1,85 @@
+use std::sync::Arc;
+use crossbeam_channel::Receiver;
+use magma::{
+	device,
+	Device,
+	DeviceOwned,
+	command,
+	pipeline,
+	mem::Allocator
+};
+use super::{
+	Query,
+	ownership::{
+		self,
+		Acquisitions
+	}
+};
+
+/// Inline loader.
+///
+/// Processes the loader queries directly in the command buffers of the queue user,
+/// for devices exposing a single queue shared with the render worker.
+/// Uploads are then executed with the recorded commands,
+/// and each loading is resolved once its command buffer has been executed and dropped.
+pub struct Inline<A: Allocator> {
+	allocator: A,
+	queue: device::Queue,
+	queries: Receiver<Query>,
+	acquisitions: Acquisitions
+}
+
+impl<A: Allocator> Inline<A> {
+	pub(crate) fn new(allocator: A, queue: device::Queue, queries: Receiver<Query>, acquisitions: &Acquisitions) -> Self {
+		Self {
+			allocator,
+			queue,
+			queries,
+			acquisitions: acquisitions.clone()
+		}
+	}
+
+	fn device(&self) -> &Arc<Device> {
+		self.queue.device()
+	}
+
+	/// Checks if some queries are waiting to be recorded.
+	pub fn is_pending(&self) -> bool {
+		!self.queries.is_empty()
+	}
+
+	/// Record every pending query.
+	///
+	/// The commands must be submitted to the queue given to [`Loader::inline`](super::Loader::inline).
+	/// The uploads are made visible to the first use of each buffer, in the following commands.
+	pub fn record<B: command::Buffer>(&mut self, commands: &mut command::buffer::Recorder<B>) {
+		let device = self.device().clone();
+		let mut uploads = Vec::new();
+		for query in self.queries.try_iter() {
+			uploads.extend(query.process(
+				&device,
+				&self.queue,
+				&mut self.allocator,
+				&self.acquisitions,
+				commands
+			))
+		}
+
+		for (buffer, size, usage) in uploads {
+			let (stage, access) = ownership::first_use(usage);
+			commands.pipeline_barrier(
+				pipeline::Stage::Transfer,
+				stage,
+				&[command::buffer::BufferMemoryBarrier {
+					buffer,
+					offset: 0,
+					size,
+					src_access: command::buffer::Access::TransferWrite,
+					dst_access: access,
+					src_queue_family: command::buffer::QUEUE_FAMILY_IGNORED,
+					dst_queue_family: command::buffer::QUEUE_FAMILY_IGNORED
+				}]
+			)
+		}
+	}
+}
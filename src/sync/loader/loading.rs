@@ -8,7 +8,7 @@ use once_cell::sync::OnceCell;
 pub struct Loading<T>(Arc<OnceCell<Arc<T>>>);
 
 impl<T> Loading<T> {
-	pub fn new() -> (Self, Handle<T>) where T: 'static {
+	pub fn new() -> (Self, Handle<T>) where T: 'static + Send + Sync {
 		let inner = Arc::new(OnceCell::new());
 		let this = Self(inner.clone());
 		let handle = Handle(Box::new(move |value| {
@@ -17,7 +17,7 @@ impl<T> Loading<T> {
 		(this, handle)
 	}
 
-	pub fn mapped<U, F>(f: F) -> (Self, Handle<U>) where T: 'static, F: 'static + FnOnce(U) -> T {
+	pub fn mapped<U, F>(f: F) -> (Self, Handle<U>) where T: 'static + Send + Sync, F: 'static + Send + FnOnce(U) -> T {
 		let inner = Arc::new(OnceCell::new());
 		let this = Self(inner.clone());
 		let handle = Handle(Box::new(move |value| {
@@ -31,9 +31,21 @@ impl<T> Loading<T> {
 	}
 }
 
-pub struct Handle<T>(Box<dyn FnOnce(T) -> ()>);
+/// Loading resolver.
+///
+/// Can be sent to the thread producing the value.
+pub struct Handle<T>(Box<dyn Send + FnOnce(T) -> ()>);
 
 impl<T> Handle<T> {
+	/// Call `f` with the value, right before the loading is resolved.
+	pub fn inspect<F: 'static + Send + FnOnce(&T)>(self, f: F) -> Handle<T> where T: 'static {
+		let set = self.0;
+		Handle(Box::new(move |value| {
+			f(&value);
+			set(value)
+		}))
+	}
+
 	pub fn prepare(self, value: T) -> Prepared<T> {
 		Prepared {
 			f: ManuallyDrop::new(self.0),
@@ -43,7 +55,7 @@ impl<T> Handle<T> {
 }
 
 pub struct Prepared<T> {
-	f: ManuallyDrop<Box<dyn FnOnce(T) -> ()>>,
+	f: ManuallyDrop<Box<dyn Send + FnOnce(T) -> ()>>,
 	value: ManuallyDrop<T>
 }
 
@@ -1,50 +1,120 @@
+use std::sync::Arc;
 use crossbeam_channel::{
 	Sender,
 };
 use magma::{
 	device,
+	DeviceOwned,
 	mem::{
 		Allocator,
 		buffer
 	},
-	sync
+	sync::{
+		self,
+		semaphore
+	}
 };
 
 mod query;
 mod thread;
 mod worker;
+mod inline;
 pub mod loading;
+pub mod ownership;
 
 pub use query::Query;
 pub use thread::Thread;
 pub use worker::Worker;
+pub(crate) use worker::{
+	Future,
+	Futures
+};
+pub use inline::Inline;
 pub use loading::Loading;
+pub use ownership::Acquisitions;
 
 pub struct Loader {
-	channel: Sender<Query>
+	channel: Sender<Query>,
+	acquisitions: Acquisitions
 }
 
 impl Loader {
 	pub fn new<A: Allocator>(allocator: A, transfert_queue: device::Queue) -> (Self, Thread<A>, Worker) {
+		Self::build(allocator, transfert_queue, None)
+	}
+
+	/// Create a loader whose thread signals a timeline semaphore for each flush.
+	///
+	/// The worker then reads the semaphore counter once per cycle,
+	/// instead of checking the fence of every pending batch.
+	/// Falls back to fences if timeline semaphores are not supported by the device.
+	pub fn with_timeline<A: Allocator>(allocator: A, transfert_queue: device::Queue) -> (Self, Thread<A>, Worker) {
+		let timeline = match semaphore::Timeline::new(transfert_queue.device(), 0) {
+			Ok(timeline) => Some(Arc::new(timeline)),
+			Err(e) => {
+				log::warn!("timeline semaphores unavailable, falling back to fences: {:?}", e);
+				None
+			}
+		};
+
+		Self::build(allocator, transfert_queue, timeline)
+	}
+
+	fn build<A: Allocator>(allocator: A, transfert_queue: device::Queue, timeline: Option<Arc<semaphore::Timeline>>) -> (Self, Thread<A>, Worker) {
 		let (queries_sender, queries_receiver) = crossbeam_channel::unbounded();
 		
-		let worker = Worker::new();
+		let worker = match &timeline {
+			Some(timeline) => Worker::with_timeline(timeline.clone()),
+			None => Worker::new()
+		};
+
+		let acquisitions = Acquisitions::new();
 
 		let thread = Thread::new(
 			allocator,
 			transfert_queue,
 			queries_receiver,
-			worker.pending_futures()
+			worker.pending_futures(),
+			&acquisitions,
+			timeline
 		);
 
 		let loader = Self {
-			channel: queries_sender
+			channel: queries_sender,
+			acquisitions
 		};
 
 		(loader, thread, worker)
 	}
 
-	pub fn load_untyped<B: 'static + AsRef<[u8]>, U: Into<buffer::Usages>, S: Into<sync::SharingQueues>>(
+	/// Create a loader sharing the given queue with the render worker.
+	///
+	/// For devices without dedicated transfer queue.
+	/// No loader thread is used: queries are recorded in the command buffers of the queue user
+	/// by the returned [`Inline`] loader (see `render::Worker::set_inline_loader`),
+	/// so that submissions to the queue are never concurrent.
+	pub fn inline<A: Allocator>(allocator: A, queue: device::Queue) -> (Self, Inline<A>) {
+		let (queries_sender, queries_receiver) = crossbeam_channel::unbounded();
+		let acquisitions = Acquisitions::new();
+		let inline = Inline::new(allocator, queue, queries_receiver, &acquisitions);
+
+		let loader = Self {
+			channel: queries_sender,
+			acquisitions
+		};
+
+		(loader, inline)
+	}
+
+	/// Pending ownership acquisitions of loaded buffers.
+	///
+	/// Buffers loaded for a single queue family other than the transfer queue family
+	/// must be acquired by this family before use.
+	pub fn acquisitions(&self) -> &Acquisitions {
+		&self.acquisitions
+	}
+
+	pub fn load_untyped<B: 'static + Send + AsRef<[u8]>, U: Into<buffer::Usages>, S: Into<sync::SharingQueues>>(
 		&self,
 		data: B,
 		usage: U,
@@ -62,7 +132,7 @@ impl Loader {
 		loading
 	}
 
-	pub fn load<T: 'static + Copy, B: 'static + std::ops::Deref<Target=[T]>, U: Into<buffer::Usages>, S: Into<sync::SharingQueues>>(
+	pub fn load<T: 'static + Copy + Send + Sync, B: 'static + Send + std::ops::Deref<Target=[T]>, U: Into<buffer::Usages>, S: Into<sync::SharingQueues>>(
 		&self,
 		data: B,
 		usage: U,
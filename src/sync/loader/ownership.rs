@@ -0,0 +1,127 @@
+//! Queue family ownership transfers.
+//!
+//! Buffers used by a single queue family are created in exclusive sharing mode,
+//! owned by the transfer queue family while they are uploaded.
+//! The loader thread records the release barrier after the upload,
+//! and the acquire barrier must then be recorded on the destination queue
+//! before the buffer is first used, with [`Acquisitions::record`].
+use std::{
+	sync::Arc,
+	collections::HashMap
+};
+use magma::{
+	device,
+	command,
+	pipeline,
+	mem::buffer
+};
+use parking_lot::Mutex;
+
+/// Stage and accesses of the first use of a buffer, given its usage.
+///
+/// Uniform and storage buffers may be read by any shader stage of the destination queue.
+pub(crate) fn first_use(usage: buffer::Usages) -> (pipeline::Stage, command::buffer::Access) {
+	let accesses = [
+		(buffer::Usage::VertexBuffer, command::buffer::Access::VertexAttributeRead),
+		(buffer::Usage::IndexBuffer, command::buffer::Access::IndexRead),
+		(buffer::Usage::UniformBuffer, command::buffer::Access::UniformRead),
+		(buffer::Usage::StorageBuffer, command::buffer::Access::ShaderRead | command::buffer::Access::ShaderWrite)
+	];
+
+	let access = accesses.iter()
+		.filter(|(u, _)| usage.contains(*u))
+		.fold(command::buffer::Access::None, |access, (_, a)| access | *a);
+
+	let stage = if usage.contains(buffer::Usage::UniformBuffer) || usage.contains(buffer::Usage::StorageBuffer) {
+		pipeline::Stage::AllCommands
+	} else {
+		pipeline::Stage::VertexInput
+	};
+
+	(stage, access)
+}
+
+/// Pending ownership acquisition.
+#[derive(Clone)]
+pub(crate) struct Acquisition {
+	buffer: buffer::VulkanBuffer,
+	size: u64,
+	usage: buffer::Usages,
+	src_family: u32,
+	dst_family: u32
+}
+
+impl Acquisition {
+	pub fn new(buffer: buffer::VulkanBuffer, size: u64, usage: buffer::Usages, src_family: u32, dst_family: u32) -> Self {
+		Self {
+			buffer,
+			size,
+			usage,
+			src_family,
+			dst_family
+		}
+	}
+
+	fn barrier(&self, src_access: command::buffer::Access, dst_access: command::buffer::Access) -> command::buffer::BufferMemoryBarrier {
+		command::buffer::BufferMemoryBarrier {
+			buffer: self.buffer,
+			offset: 0,
+			size: self.size,
+			src_access,
+			dst_access,
+			src_queue_family: self.src_family,
+			dst_queue_family: self.dst_family
+		}
+	}
+
+	/// Record the release barrier, on the transfer queue.
+	pub fn record_release<B: command::Buffer>(&self, commands: &mut command::buffer::Recorder<B>) {
+		commands.pipeline_barrier(
+			pipeline::Stage::Transfer,
+			pipeline::Stage::BottomOfPipe,
+			&[self.barrier(command::buffer::Access::TransferWrite, command::buffer::Access::None)]
+		)
+	}
+
+	/// Record the acquire barrier, on the destination queue.
+	fn record_acquire<B: command::Buffer>(&self, commands: &mut command::buffer::Recorder<B>) {
+		let (stage, access) = first_use(self.usage);
+		commands.pipeline_barrier(
+			pipeline::Stage::TopOfPipe,
+			stage,
+			&[self.barrier(command::buffer::Access::None, access)]
+		)
+	}
+}
+
+/// Pending ownership acquisitions of uploaded buffers.
+///
+/// An acquisition becomes pending once its upload has been executed,
+/// right before the buffer loading is resolved.
+/// Acquisitions are stored per destination queue family,
+/// and each user of a queue records the acquisitions of its own family.
+#[derive(Clone)]
+pub struct Acquisitions(Arc<Mutex<HashMap<u32, Vec<Acquisition>>>>);
+
+impl Acquisitions {
+	pub(crate) fn new() -> Self {
+		Self(Arc::new(Mutex::new(HashMap::new())))
+	}
+
+	pub(crate) fn push(&self, acquisition: Acquisition) {
+		let mut pending = self.0.lock();
+		pending.entry(acquisition.dst_family).or_default().push(acquisition)
+	}
+
+	/// Record the pending acquire barriers of buffers owned by the family of the given queue.
+	///
+	/// Must be recorded in the queue command buffers before any loaded buffer is used.
+	/// Acquisitions of other families are left to the users of their queues.
+	pub fn record<B: command::Buffer>(&self, commands: &mut command::buffer::Recorder<B>, queue: &device::Queue) {
+		let acquisitions = self.0.lock().remove(&queue.family().index());
+
+		for acquisition in acquisitions.into_iter().flatten() {
+			acquisition.record_acquire(commands)
+		}
+	}
+}
@@ -15,12 +15,18 @@ use magma::{
 	},
 	sync
 };
-use super::loading;
+use super::{
+	loading,
+	ownership::{
+		Acquisition,
+		Acquisitions
+	}
+};
 
 pub enum Query {
 	Flush,
 	Load {
-		data: Box<dyn AsRef<[u8]>>,
+		data: Box<dyn Send + AsRef<[u8]>>,
 		usage: buffer::Usages,
 		sharing_queues: sync::SharingQueues,
 		buffer: loading::Handle<buffer::Bound>
@@ -33,13 +39,20 @@ pub enum Query {
 
 impl Query {
 	/// Process a single query.
+	///
+	/// Buffers shared by a single queue family are created in exclusive sharing mode.
+	/// If this family is not the transfer queue family,
+	/// the buffer ownership is released here and must be acquired with `acquisitions`.
+	///
+	/// Returns the uploaded buffer, its size and usage, if any.
 	pub fn process<A: Allocator, B: command::Buffer>(
 		self,
 		device: &Arc<Device>,
 		transfert_queue: &device::Queue,
 		allocator: &mut A,
+		acquisitions: &Acquisitions,
 		commands: &mut command::buffer::Recorder<B>
-	) {
+	) -> Option<(buffer::VulkanBuffer, u64, buffer::Usages)> {
 		match self {
 			Query::Load { data, usage, mut sharing_queues, buffer } => {
 				let src = (*data).as_ref();
@@ -51,7 +64,19 @@ impl Query {
 					transfert_queue
 				).expect("unable to create staging buffer");
 		
-				sharing_queues.insert(transfert_queue);
+				let transfert_family = transfert_queue.family().index();
+				let owner_family = match sharing_queues.families() {
+					[family] => Some(*family),
+					_ => None
+				};
+
+				match owner_family {
+					Some(_) => {
+						// Exclusive buffer, owned by the transfer queue family during the upload.
+						sharing_queues = transfert_queue.into()
+					},
+					None => sharing_queues.insert(transfert_queue)
+				}
 		
 				let remote_buffer = buffer::Unbound::new(
 					device,
@@ -70,6 +95,23 @@ impl Query {
 					Err((_, e)) => panic!("unable to bind staging buffer memory: {:?}", e)
 				};
 
+				let remote_handle = remote_buffer.handle();
+				let release = match owner_family {
+					Some(family) if family != transfert_family => {
+						Some(Acquisition::new(remote_handle, src.len() as u64, usage, transfert_family, family))
+					},
+					_ => None
+				};
+
+				let buffer = match &release {
+					Some(release) => {
+						let acquisitions = acquisitions.clone();
+						let acquisition = release.clone();
+						buffer.inspect(move |_| acquisitions.push(acquisition))
+					},
+					None => buffer
+				};
+
 				let remote_buffer: loading::Prepared<buffer::Bound> = match unsafe { remote_buffer.bind(remote_slot) } {
 					Ok(bound) => buffer.prepare(bound),
 					Err((_, e)) => panic!("unable to bind remote buffer memory: {:?}", e)
@@ -83,9 +125,15 @@ impl Query {
 					src_offset: 0,
 					dst_offset: 0,
 					size: src.len() as u64
-				}])
+				}]);
+
+				if let Some(release) = release {
+					release.record_release(commands)
+				}
+
+				Some((remote_handle, src.len() as u64, usage))
 			},
-			Query::Flush => ()
+			Query::Flush => None
 		}
 	}
 }
\ No newline at end of file
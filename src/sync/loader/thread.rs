@@ -8,7 +8,10 @@ use magma::{
 	DeviceOwned,
 	mem::Allocator,
 	command::Buffer,
-	sync::Task
+	sync::{
+		Task,
+		semaphore
+	}
 };
 use crate::sync::{
 	FencePool,
@@ -16,7 +19,8 @@ use crate::sync::{
 };
 use super::{
 	Query,
-	worker
+	worker,
+	ownership::Acquisitions
 };
 
 /// Loader thread.
@@ -27,9 +31,13 @@ pub struct Thread<A: Allocator> {
 	transfert_queue: device::Queue,
 	queries: Receiver<Query>,
 	worker_futures: worker::Futures,
+	acquisitions: Acquisitions,
 	fence_pool: FencePool,
 	command_buffer_pool: CommandBufferPool,
-	prepared_queries: Vec<Query>
+	prepared_queries: Vec<Query>,
+
+	/// Timeline semaphore signaled by each flush, with its last value.
+	timeline: Option<(Arc<semaphore::Timeline>, u64)>
 }
 
 impl<A: Allocator> Thread<A> {
@@ -37,7 +45,9 @@ impl<A: Allocator> Thread<A> {
 		allocator: A,
 		transfert_queue: device::Queue,
 		queries: Receiver<Query>,
-		worker_futures: &worker::Futures
+		worker_futures: &worker::Futures,
+		acquisitions: &Acquisitions,
+		timeline: Option<Arc<semaphore::Timeline>>
 	) -> Self {
 		let fence_pool = FencePool::new(transfert_queue.device());
 		let command_buffer_pool = CommandBufferPool::new(&transfert_queue).expect("unable to create command buffer pool");
@@ -47,9 +57,11 @@ impl<A: Allocator> Thread<A> {
 			transfert_queue,
 			queries,
 			worker_futures: worker_futures.clone(),
+			acquisitions: acquisitions.clone(),
 			fence_pool,
 			command_buffer_pool,
-			prepared_queries: Vec::new()
+			prepared_queries: Vec::new(),
+			timeline: timeline.map(|timeline| (timeline, 0))
 		}
 	}
 
@@ -66,21 +78,32 @@ impl<A: Allocator> Thread<A> {
 					&device,
 					&self.transfert_queue,
 					&mut self.allocator,
+					&self.acquisitions,
 					commands
-				)
+				);
 			}
 		}).expect("unable to record command buffer");
 
-		let fence = self.fence_pool.get().expect("unable to create fence");
-		let (_, future) = self.transfert_queue.submit(recorded_command_buffer).then_signal_fence(fence).expect("unable to submit command buffer");
-		self.worker_futures.push(worker::Future::new(future))
+		match &mut self.timeline {
+			Some((timeline, value)) => {
+				*value += 1;
+				let (_, future) = self.transfert_queue.submit(recorded_command_buffer).then_signal_timeline(timeline, *value).expect("unable to submit command buffer");
+				self.worker_futures.push(worker::Future::timeline(future, *value))
+			},
+			None => {
+				let fence = self.fence_pool.get().expect("unable to create fence");
+				let (_, future) = self.transfert_queue.submit(recorded_command_buffer).then_signal_fence(fence).expect("unable to submit command buffer");
+				self.worker_futures.push(worker::Future::new(future))
+			}
+		}
 	}
 
 	fn prepare_query(&mut self, query: &Query) {
 		// ...
 	}
 
-	fn run(&mut self) {
+	/// Run the loader thread, until every loader has been dropped.
+	pub fn run(&mut self) {
 		loop {
 			match self.queries.recv() {
 				Ok(Query::Flush) => {
@@ -1,32 +1,79 @@
 use std::sync::Arc;
 use magma::{
 	sync::{
-		future::SignalFence
+		future::SignalFence,
+		semaphore
 	}
 };
 use crossbeam_queue::SegQueue;
 
 pub(crate) struct Future {
-	inner: Box<dyn SignalFence>
+	kind: Kind,
+
+	/// Functions called once the future is signaled.
+	on_complete: Vec<Box<dyn Send + FnOnce()>>
+}
+
+enum Kind {
+	/// Submission signaling a fence.
+	Fence(Box<dyn Send + SignalFence>),
+
+	/// Submission signaling the loader timeline semaphore with the given value.
+	///
+	/// The submission future is only kept alive until the value is reached.
+	Timeline(Box<dyn Send>, u64)
 }
 
 impl Future {
-	pub fn new<F: 'static + SignalFence>(future: F) -> Self {
+	pub fn new<F: 'static + Send + SignalFence>(future: F) -> Self {
 		Self {
-			inner: Box::new(future)
+			kind: Kind::Fence(Box::new(future)),
+			on_complete: Vec::new()
+		}
+	}
+
+	pub fn timeline<F: 'static + Send>(future: F, value: u64) -> Self {
+		Self {
+			kind: Kind::Timeline(Box::new(future), value),
+			on_complete: Vec::new()
+		}
+	}
+
+	/// Call the given function once the future is signaled.
+	pub fn on_complete<F: 'static + Send + FnOnce()>(mut self, f: F) -> Self {
+		self.on_complete.push(Box::new(f));
+		self
+	}
+
+	/// Checks if the future is signaled, given the current value of the timeline semaphore.
+	pub fn is_signaled(&self, timeline_value: Option<u64>) -> bool {
+		match &self.kind {
+			Kind::Fence(future) => future.is_signaled().expect("fence error"),
+			Kind::Timeline(_, value) => match timeline_value {
+				Some(current) => *value <= current,
+				None => false
+			}
 		}
 	}
 
-	pub fn is_signaled(&self) -> bool {
-		self.inner.is_signaled().expect("fence error")
+	/// Complete the signaled future.
+	fn complete(self) {
+		for f in self.on_complete {
+			f()
+		}
 	}
 }
 
 pub(crate) type Futures = Arc<SegQueue<Future>>;
 
 /// Fence synchronization.
+///
+/// When a timeline semaphore is used by the loader thread,
+/// its counter is read once per cycle to retire every batch up to its value.
+/// Otherwise, the fence of each batch is checked.
 pub struct Worker {
 	pending_futures: Futures,
+	timeline: Option<Arc<semaphore::Timeline>>,
 	futures: Vec<Future>,
 	signaled: Vec<Future>
 }
@@ -35,11 +82,20 @@ impl Worker {
 	pub fn new() -> Self {
 		Worker {
 			pending_futures: Arc::new(SegQueue::new()),
+			timeline: None,
 			futures: Vec::new(),
 			signaled: Vec::new()
 		}
 	}
 
+	/// Create a worker retiring batches with the given timeline semaphore.
+	pub fn with_timeline(timeline: Arc<semaphore::Timeline>) -> Self {
+		Worker {
+			timeline: Some(timeline),
+			..Self::new()
+		}
+	}
+
 	pub(crate) fn pending_futures(&self) -> &Futures {
 		&self.pending_futures
 	}
@@ -47,7 +103,8 @@ impl Worker {
 
 impl<T> cycles::Worker<T> for Worker {
 	fn cycle(&mut self, _: &T) {
-		self.signaled = self.futures.drain_filter(|f| f.is_signaled()).collect();
+		let timeline_value = self.timeline.as_ref().map(|timeline| timeline.value().expect("semaphore error"));
+		self.signaled.extend(self.futures.drain_filter(|f| f.is_signaled(timeline_value)));
 	}
 
 	fn apply(&mut self, _: &mut T) {
@@ -56,7 +113,7 @@ impl<T> cycles::Worker<T> for Worker {
 		}
 		
 		for future in self.signaled.drain(..) {
-			std::mem::drop(future)
+			future.complete()
 		}
 	}
 }
\ No newline at end of file
@@ -1,14 +1,21 @@
 use std::{
-	rc::Rc,
-	sync::Arc
+	sync::Arc,
+	convert::TryInto
 };
+use glam::Vec3;
 use magma::{
 	mem::{
 		buffer
 	},
 	sync::SharingQueues
 };
-use crate::util::RefMap;
+use crate::{
+	util::RefMap,
+	bounds::{
+		Aabb,
+		Sphere
+	}
+};
 use crate::sync::{
 	Loader,
 	loader::Loading,
@@ -18,8 +25,12 @@ use once_cell::unsync::OnceCell;
 pub mod projection;
 pub use projection::Projection;
 
+/// Size of a vertex in the geometry source.
+const VERTEX_STRIDE: usize = std::mem::size_of::<Vec3>();
+
 pub struct Geometry {
-	source: Rc<geometer::AbstractGeometry>,
+	source: Arc<geometer::AbstractGeometry>,
+	bounds: OnceCell<Option<(Aabb, Sphere)>>,
 	vertex_buffer: OnceCell<Loading<buffer::Bound>>,
 	index_buffers: Vec<OnceCell<Loading<buffer::Typed<u32>>>>,
 }
@@ -31,12 +42,102 @@ impl Geometry {
 		index_buffers.resize_with(index_buffer_count, || OnceCell::new());
 
 		Self {
-			source: Rc::new(source),
+			source: Arc::new(source),
+			bounds: OnceCell::new(),
 			vertex_buffer: OnceCell::new(),
 			index_buffers
 		}
 	}
 
+	/// Identifier of the geometry buffers.
+	///
+	/// Two geometries sharing the same source have the same identifier.
+	pub fn id(&self) -> usize {
+		Arc::as_ptr(&self.source) as usize
+	}
+
+	/// Number of vertices.
+	pub fn vertex_count(&self) -> usize {
+		self.source.vertices().len() / VERTEX_STRIDE
+	}
+
+	/// Vertex positions, in local space.
+	pub fn positions(&self) -> impl '_ + Clone + Iterator<Item=Vec3> {
+		self.source.vertices().chunks_exact(VERTEX_STRIDE).map(read_position)
+	}
+
+	/// Position of the given vertex, in local space.
+	pub fn position(&self, index: usize) -> Vec3 {
+		let offset = index * VERTEX_STRIDE;
+		read_position(&self.source.vertices()[offset..(offset + VERTEX_STRIDE)])
+	}
+
+	/// Number of available precisions.
+	pub fn precision_count(&self) -> usize {
+		self.source.precisions().len()
+	}
+
+	/// The given precision clamped to the available precisions,
+	/// or `None` if there is none.
+	fn clamped_precision(&self, precision: usize) -> Option<usize> {
+		self.precision_count().checked_sub(1).map(|last| std::cmp::min(precision, last))
+	}
+
+	/// Vertex indices at the given precision.
+	///
+	/// The precision is clamped to the available precisions.
+	/// Returns no index if the geometry has no precision.
+	pub fn indices(&self, precision: usize) -> &[u32] {
+		match self.clamped_precision(precision) {
+			Some(precision) => self.source.precisions()[precision].indices(),
+			None => &[]
+		}
+	}
+
+	/// Triangles at the given precision, assuming the indices form a triangle list.
+	pub fn triangles(&self, precision: usize) -> impl '_ + Iterator<Item=[Vec3; 3]> {
+		self.indices(precision).chunks_exact(3).map(move |t| self.triangle(t))
+	}
+
+	/// Triangles at the given precision, assuming the indices form a triangle strip.
+	///
+	/// The winding of every other triangle is reversed.
+	pub fn strip_triangles(&self, precision: usize) -> impl '_ + Iterator<Item=[Vec3; 3]> {
+		self.indices(precision).windows(3).map(move |t| self.triangle(t))
+	}
+
+	fn triangle(&self, indices: &[u32]) -> [Vec3; 3] {
+		[
+			self.position(indices[0] as usize),
+			self.position(indices[1] as usize),
+			self.position(indices[2] as usize)
+		]
+	}
+
+	fn computed_bounds(&self) -> Option<&(Aabb, Sphere)> {
+		self.bounds.get_or_init(|| {
+			let aabb = Aabb::from_points(self.positions())?;
+			let sphere = Sphere::from_points(self.positions())?;
+			Some((aabb, sphere))
+		}).as_ref()
+	}
+
+	/// Local-space bounding box.
+	///
+	/// Computed from the geometry vertices on first call, and cached.
+	/// Returns `None` if the geometry has no vertices.
+	pub fn bounds(&self) -> Option<&Aabb> {
+		self.computed_bounds().map(|(aabb, _)| aabb)
+	}
+
+	/// Local-space bounding sphere.
+	///
+	/// Computed from the geometry vertices on first call, and cached.
+	/// Returns `None` if the geometry has no vertices.
+	pub fn bounding_sphere(&self) -> Option<&Sphere> {
+		self.computed_bounds().map(|(_, sphere)| sphere)
+	}
+
 	pub fn vertex_buffer(&self, loader: &Loader, sharing_queues: SharingQueues) -> Option<&Arc<buffer::Bound>> {
 		self.vertex_buffer.get_or_init(move || {
 			let vertices: RefMap<_, _, [u8]> = RefMap::new(self.source.clone(), |s| s.vertices());
@@ -45,7 +146,7 @@ impl Geometry {
 	}
 
 	pub fn index_buffer(&self, precision: usize, loader: &Loader, sharing_queues: SharingQueues) -> Option<&Arc<buffer::Typed<u32>>> {
-		let precision = std::cmp::min(precision, self.source.precisions().len());
+		let precision = self.clamped_precision(precision)?;
 
 		let index_buffer = &self.index_buffers[precision];
 		index_buffer.get_or_init(move || {
@@ -53,4 +154,17 @@ impl Geometry {
 			loader.load(indices, buffer::Usage::IndexBuffer, sharing_queues)
 		}).get()
 	}
-}
\ No newline at end of file
+
+	/// Checks if some requested buffers are still being loaded.
+	///
+	/// Buffers are only requested on first use, so this is `false` for a geometry never drawn.
+	pub fn is_loading(&self) -> bool {
+		self.vertex_buffer.get().map_or(false, |loading| loading.get().is_none())
+			|| self.index_buffers.iter().any(|cell| cell.get().map_or(false, |loading| loading.get().is_none()))
+	}
+}
+
+fn read_position(vertex: &[u8]) -> Vec3 {
+	let coordinate = |i: usize| f32::from_ne_bytes(vertex[(i * 4)..(i * 4 + 4)].try_into().unwrap());
+	Vec3::new(coordinate(0), coordinate(1), coordinate(2))
+}
@@ -0,0 +1,26 @@
+use super::{
+	Projection,
+	VertexShader
+};
+
+/// Billboard projection.
+///
+/// The geometry is placed in view space around the object origin,
+/// so that it always faces the camera.
+pub struct Billboard {
+	shader: VertexShader
+}
+
+impl Billboard {
+	pub fn new(shader: VertexShader) -> Billboard {
+		Billboard {
+			shader
+		}
+	}
+}
+
+impl Projection for Billboard {
+	fn shader(&self) -> &VertexShader {
+		&self.shader
+	}
+}
@@ -0,0 +1,27 @@
+use super::{
+	Projection,
+	VertexShader
+};
+
+/// Instanced projection.
+///
+/// Applies the per-instance transformation of [`Instanced`](crate::view::Instanced) views
+/// before the standard projection.
+/// Meant to be used with the shader in `shaders/instanced.vert`.
+pub struct Instanced {
+	shader: VertexShader
+}
+
+impl Instanced {
+	pub fn new(shader: VertexShader) -> Instanced {
+		Instanced {
+			shader
+		}
+	}
+}
+
+impl Projection for Instanced {
+	fn shader(&self) -> &VertexShader {
+		&self.shader
+	}
+}
@@ -3,8 +3,14 @@ use magma::pipeline::shader;
 use glam::Mat4;
 
 mod standard;
+mod billboard;
+mod instanced;
+mod particle;
 
 pub use standard::Standard;
+pub use billboard::Billboard;
+pub use instanced::Instanced;
+pub use particle::Particle;
 
 pub trait Projection: Sync + Send {
 	fn shader(&self) -> &VertexShader;
@@ -13,6 +19,11 @@ pub trait Projection: Sync + Send {
 pub struct VertexShader(Arc<shader::Module>);
 
 impl VertexShader {
+	/// Create a vertex shader from a compiled SPIR-V module, with a `main` entry point.
+	pub fn new(module: Arc<shader::Module>) -> VertexShader {
+		VertexShader(module)
+	}
+
 	// pub unsafe fn new(device: &Arc<Device>, vspir: &[u8], input: shader::Input, output: shader::Output) -> VertexShader {
 	// 	VertexShader(Shader::new(device, vspir, GraphicsShaderType::Vertex, input, output))
 	// }
@@ -24,11 +35,42 @@ impl VertexShader {
 	}
 }
 
+/// Size of the projection push constants, read by every projection shader at offset `0`.
+pub const PROJECTION_PUSH_CONSTANT_SIZE: u32 = 128;
+
+/// Projection matrices, pushed to the projection shaders.
+#[repr(C)]
+#[derive(Clone, Copy, PartialEq, Debug)]
 pub struct CameraProjection {
 	pub modelview: Mat4,
 	pub proj: Mat4
 }
 
+impl CameraProjection {
+	pub fn new(modelview: Mat4, proj: Mat4) -> CameraProjection {
+		CameraProjection {
+			modelview,
+			proj
+		}
+	}
+
+	/// Projection of an object placed with the given local transformation.
+	pub fn transformed(&self, transformation: &Mat4) -> CameraProjection {
+		CameraProjection {
+			modelview: self.modelview * *transformation,
+			proj: self.proj
+		}
+	}
+
+	/// Push constants data.
+	pub fn as_bytes(&self) -> &[u8] {
+		unsafe {
+			// Safe because `CameraProjection` is `repr(C)` and only made of floats.
+			std::slice::from_raw_parts(self as *const CameraProjection as *const u8, std::mem::size_of::<CameraProjection>())
+		}
+	}
+}
+
 impl Default for CameraProjection {
 	fn default() -> CameraProjection {
 		CameraProjection {
@@ -37,34 +79,3 @@ impl Default for CameraProjection {
 		}
 	}
 }
-
-// /// Push-constant used to store the projection matrices.
-// const PROJECTION_PC_DESCRIPTOR: PipelineLayoutDescPcRange = PipelineLayoutDescPcRange {
-// 	offset: 0,
-// 	size: 128,
-// 	stages: ShaderStages {
-// 		vertex: true,
-// 		tessellation_control: false,
-// 		tessellation_evaluation: false,
-// 		geometry: false,
-// 		fragment: false,
-// 		compute: false
-// 	}
-// };
-
-// const PROJECTION_BINDING_DESCRIPTOR: DescriptorDesc = DescriptorDesc {
-// 	ty: DescriptorDesc::Buffer(DescriptorBufferDesc {
-// 		dynamic: None,
-// 		storage: false
-// 	}),
-// 	array_count: 1,
-// 	stages: ShaderStages {
-// 		vertex: true,
-// 		tessellation_control: false,
-// 		tessellation_evaluation: false,
-// 		geometry: false,
-// 		fragment: false,
-// 		compute: false
-// 	},
-// 	readonly: true
-// };
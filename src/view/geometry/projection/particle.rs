@@ -0,0 +1,27 @@
+use super::{
+	Projection,
+	VertexShader
+};
+
+/// Particle projection.
+///
+/// Places a camera-facing quad on each particle of a [`Particles`](crate::view::Particles) view,
+/// scaled by the particle size.
+/// Meant to be used with the shader in `shaders/particle.vert`.
+pub struct Particle {
+	shader: VertexShader
+}
+
+impl Particle {
+	pub fn new(shader: VertexShader) -> Particle {
+		Particle {
+			shader
+		}
+	}
+}
+
+impl Projection for Particle {
+	fn shader(&self) -> &VertexShader {
+		&self.shader
+	}
+}
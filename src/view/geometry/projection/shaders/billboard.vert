@@ -0,0 +1,12 @@
+#version 450
+layout(push_constant) uniform Projection {
+	mat4 modelview;
+	mat4 projection;
+} pc;
+
+layout(location = 0) in vec3 position;
+
+void main() {
+	vec4 center = pc.modelview * vec4(0.0, 0.0, 0.0, 1.0);
+	gl_Position = pc.projection * (center + vec4(position.xy, 0.0, 0.0));
+}
@@ -0,0 +1,14 @@
+#version 450
+layout(push_constant) uniform Projection {
+	mat4 modelview;
+	mat4 projection;
+} pc;
+
+layout(location = 0) in vec3 position;
+
+// Per-instance transformation, one column per location.
+layout(location = 1) in mat4 instance;
+
+void main() {
+	gl_Position = pc.projection * pc.modelview * instance * vec4(position, 1.0);
+}
@@ -0,0 +1,24 @@
+#version 450
+layout(push_constant) uniform Projection {
+	mat4 modelview;
+	mat4 projection;
+} pc;
+
+// Quad corner, in `[-0.5, 0.5]`.
+layout(location = 0) in vec3 position;
+
+// Particle state, see `view/particles/shaders/simulate.comp`.
+layout(location = 1) in vec4 particle_position;
+layout(location = 2) in vec4 particle_velocity;
+layout(location = 3) in vec4 particle_color;
+layout(location = 4) in vec4 particle_size;
+
+layout(location = 0) out vec4 color;
+layout(location = 1) out vec2 uv;
+
+void main() {
+	vec4 center = pc.modelview * vec4(particle_position.xyz, 1.0);
+	gl_Position = pc.projection * (center + vec4(position.xy * particle_size.x, 0.0, 0.0));
+	color = particle_color;
+	uv = position.xy;
+}
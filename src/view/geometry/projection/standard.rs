@@ -1,8 +1,3 @@
-use std::sync::Arc;
-// use crate::shader::{
-// 	Input,
-// 	Output
-// };
 use super::{
 	Projection,
 	VertexShader
@@ -14,21 +9,11 @@ pub struct Standard {
 }
 
 impl Standard {
-	// pub fn new(device: &Arc<Device>) -> Standard {
-	// 	let vspir = include_bytes!("shaders/standard.spv");
-
-	// 	let shader = unsafe {
-	// 		let mut input = Input::empty();
-	// 		input.add("position", 0..1, Format::R32G32B32Sfloat);
-	// 		input.add_push_constant(super::PROJECTION_PC_DESCRIPTOR);
-
-	// 		VertexShader::new(device, vspir, input, Output::empty())
-	// 	};
-
-	// 	Standard {
-	// 		shader
-	// 	}
-	// }
+	pub fn new(shader: VertexShader) -> Standard {
+		Standard {
+			shader
+		}
+	}
 }
 
 impl Projection for Standard {
@@ -0,0 +1,82 @@
+use std::sync::Arc;
+use glam::Mat4;
+use magma::{
+	command,
+	framebuffer::RenderPass
+};
+use crate::render;
+use super::{
+	View,
+	geometry::projection::CameraProjection,
+	material::FragmentShader
+};
+
+/// Composite view.
+///
+/// Each sub-view is drawn with its own local transformation,
+/// relative to the group.
+pub struct Group {
+	children: Vec<(Mat4, View)>
+}
+
+impl Group {
+	/// Create a new empty group.
+	pub fn new() -> Self {
+		Self {
+			children: Vec::new()
+		}
+	}
+
+	/// Add a sub-view with the given local transformation.
+	pub fn push(&mut self, transformation: Mat4, view: View) {
+		self.children.push((transformation, view))
+	}
+
+	/// Add a sub-view with the given local transformation.
+	pub fn with(mut self, transformation: Mat4, view: View) -> Self {
+		self.push(transformation, view);
+		self
+	}
+
+	/// Number of sub-views.
+	pub fn len(&self) -> usize {
+		self.children.len()
+	}
+
+	/// Checks if the group is empty.
+	pub fn is_empty(&self) -> bool {
+		self.children.is_empty()
+	}
+
+	/// Iterate over the sub-views and their local transformation.
+	pub fn iter(&self) -> impl Iterator<Item=&(Mat4, View)> {
+		self.children.iter()
+	}
+
+	pub fn draw<C: render::Context, B: command::Buffer>(&self, context: &C, commands: &mut command::buffer::Recorder<B>, projection: &CameraProjection) {
+		for (transformation, view) in &self.children {
+			view.draw(context, commands, &projection.transformed(transformation))
+		}
+	}
+
+	/// Draw the given id for every sub-view.
+	pub fn draw_id<C: render::Context, B: command::Buffer>(
+		&self,
+		context: &C,
+		commands: &mut command::buffer::Recorder<B>,
+		projection: &CameraProjection,
+		render_pass: &Arc<RenderPass>,
+		shader: &FragmentShader,
+		id: u32
+	) {
+		for (transformation, view) in &self.children {
+			view.draw_id(context, commands, &projection.transformed(transformation), render_pass, shader, id)
+		}
+	}
+}
+
+impl Default for Group {
+	fn default() -> Self {
+		Self::new()
+	}
+}
@@ -0,0 +1,129 @@
+use std::sync::Arc;
+use glam::Mat4;
+use magma::{
+	command,
+	framebuffer::RenderPass,
+	mem::buffer
+};
+use once_cell::sync::OnceCell;
+use crate::{
+	render,
+	sync::loader::Loading
+};
+use super::{
+	Object,
+	geometry::projection::CameraProjection,
+	material::FragmentShader
+};
+
+/// Many copies of the same object, drawn with a single instanced draw call.
+///
+/// Every instance shares the geometry, projection and material of the object,
+/// and only differs by its transformation.
+/// The object projection must read the per-instance transformation,
+/// like the [`Instanced`](super::geometry::projection::Instanced) projection.
+pub struct Instanced {
+	/// Instanced object.
+	object: Object,
+
+	/// Per-instance transformations.
+	transforms: Arc<[Mat4]>,
+
+	/// Instance buffer, loaded on first draw.
+	instance_buffer: OnceCell<Loading<buffer::Typed<Mat4>>>,
+
+	/// Instance buffer of the previous transformations, and its length.
+	///
+	/// Drawn while the instance buffer of the new transformations is loading.
+	previous: Option<(Loading<buffer::Typed<Mat4>>, u32)>
+}
+
+impl Instanced {
+	/// Create a new instanced view.
+	///
+	/// The given object must have been created with [`Object::new_instanced`].
+	pub fn new<T: Into<Arc<[Mat4]>>>(object: Object, transforms: T) -> Self {
+		assert!(object.is_instanced());
+
+		Self {
+			object,
+			transforms: transforms.into(),
+			instance_buffer: OnceCell::new(),
+			previous: None
+		}
+	}
+
+	/// Instanced object.
+	pub fn object(&self) -> &Object {
+		&self.object
+	}
+
+	/// Per-instance transformations.
+	pub fn transforms(&self) -> &[Mat4] {
+		&self.transforms
+	}
+
+	/// Replace the per-instance transformations.
+	///
+	/// The new instance buffer is loaded on the next draw.
+	/// Until it is loaded, the previous transformations are drawn.
+	pub fn set_transforms<T: Into<Arc<[Mat4]>>>(&mut self, transforms: T) {
+		let len = self.transforms.len() as u32;
+		self.transforms = transforms.into();
+
+		if let Some(loading) = self.instance_buffer.take() {
+			if loading.get().is_some() {
+				self.previous = Some((loading, len))
+			}
+		}
+	}
+
+	/// Number of instances.
+	pub fn len(&self) -> usize {
+		self.transforms.len()
+	}
+
+	/// Checks if there is no instance to draw.
+	pub fn is_empty(&self) -> bool {
+		self.transforms.is_empty()
+	}
+
+	/// Instance buffer to draw, and its length.
+	fn instance_buffer<C: render::Context>(&self, context: &C) -> Option<(&Arc<buffer::Typed<Mat4>>, u32)> {
+		if self.is_empty() {
+			return None
+		}
+
+		let loading = self.instance_buffer.get_or_init(|| {
+			context.loader().load(self.transforms.clone(), buffer::Usage::VertexBuffer, context.graphics_queue())
+		});
+
+		match loading.get() {
+			Some(buffer) => Some((buffer, self.transforms.len() as u32)),
+			None => self.previous.as_ref().and_then(|(loading, len)| loading.get().map(|buffer| (buffer, *len)))
+		}
+	}
+
+	pub fn draw<C: render::Context, B: command::Buffer>(&self, context: &C, commands: &mut command::buffer::Recorder<B>, projection: &CameraProjection) {
+		if let Some((instance_buffer, len)) = self.instance_buffer(context) {
+			self.object.draw_instanced(context, commands, projection, instance_buffer, len)
+		}
+	}
+
+	/// Draw the given id for every instance.
+	///
+	/// See [`Object::draw_id`].
+	pub fn draw_id<C: render::Context, B: command::Buffer>(
+		&self,
+		context: &C,
+		commands: &mut command::buffer::Recorder<B>,
+		projection: &CameraProjection,
+		render_pass: &Arc<RenderPass>,
+		shader: &FragmentShader,
+		id: u32
+	) {
+		if let Some((instance_buffer, len)) = self.instance_buffer(context) {
+			self.object.draw_id(context, commands, projection, render_pass, shader, id, Some((instance_buffer, len)))
+		}
+	}
+}
@@ -0,0 +1,57 @@
+use std::sync::Arc;
+use magma::command;
+use crate::render;
+use super::{
+	geometry::{
+		self,
+		projection::CameraProjection
+	},
+	Geometry,
+	Material,
+	Object,
+	object::Primitive
+};
+
+/// Line segments.
+///
+/// Can be used to represent trajectories, wireframes or debugging information.
+pub struct Lines {
+	object: Object
+}
+
+impl Lines {
+	/// Create a new set of line segments.
+	///
+	/// The geometry indices are read by pairs, each pair forming a segment.
+	pub fn new(geometry: Geometry, projection: Arc<dyn geometry::Projection>, material: Arc<dyn Material>, width: f32) -> Self {
+		Self {
+			object: Object::with_primitive(geometry, projection, material, Primitive::Lines(width))
+		}
+	}
+
+	/// Create a new polyline.
+	///
+	/// Each geometry index is connected to the next one.
+	pub fn strip(geometry: Geometry, projection: Arc<dyn geometry::Projection>, material: Arc<dyn Material>, width: f32) -> Self {
+		Self {
+			object: Object::with_primitive(geometry, projection, material, Primitive::LineStrip(width))
+		}
+	}
+
+	/// Underlying object.
+	pub fn object(&self) -> &Object {
+		&self.object
+	}
+
+	/// Line width.
+	pub fn width(&self) -> f32 {
+		match self.object.primitive() {
+			Primitive::Lines(width) | Primitive::LineStrip(width) => width,
+			_ => unreachable!()
+		}
+	}
+
+	pub fn draw<C: render::Context, B: command::Buffer>(&self, context: &C, commands: &mut command::buffer::Recorder<B>, projection: &CameraProjection) {
+		self.object.draw(context, commands, projection)
+	}
+}
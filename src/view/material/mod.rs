@@ -6,15 +6,52 @@ use magma::pipeline::shader;
 // };
 
 mod depth;
+mod particle;
 pub use depth::Depth;
+pub use particle::Particle;
+
+/// Blending of a material with what is behind it.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Blending {
+	/// Standard alpha blending.
+	Alpha,
+
+	/// Colors are added to what is behind, weighted by their alpha.
+	///
+	/// Used for emissive effects such as sparks or fire.
+	Additive
+}
+
+impl Default for Blending {
+	fn default() -> Self {
+		Blending::Alpha
+	}
+}
 
 pub trait Material: Sync + Send {
 	fn shader(&self) -> &FragmentShader;
+
+	/// Checks if the material is blended with what is behind it.
+	///
+	/// Blended materials are drawn after opaque ones, back to front.
+	fn is_blended(&self) -> bool {
+		false
+	}
+
+	/// Blending of the material, when it is blended.
+	fn blending(&self) -> Blending {
+		Blending::Alpha
+	}
 }
 
 pub struct FragmentShader(Arc<shader::Module>);
 
 impl FragmentShader {
+	/// Create a fragment shader from a compiled SPIR-V module, with a `main` entry point.
+	pub fn new(module: Arc<shader::Module>) -> FragmentShader {
+		FragmentShader(module)
+	}
+
 	// pub unsafe fn new(device: &Arc<Device>, vspir: &[u8], input: shader::Input, output: shader::Output) -> FragmentShader {
 	// 	FragmentShader(Shader::new(device, vspir, GraphicsShaderType::Fragment, input, output))
 	// }
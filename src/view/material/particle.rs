@@ -0,0 +1,37 @@
+use super::{
+	Material,
+	FragmentShader,
+	Blending
+};
+
+/// Particle material.
+///
+/// Draws each particle as a soft disc of the color computed by the simulation.
+/// Meant to be used with the shader in `shaders/particle.frag`.
+pub struct Particle {
+	shader: FragmentShader,
+	blending: Blending
+}
+
+impl Particle {
+	pub fn new(shader: FragmentShader, blending: Blending) -> Particle {
+		Particle {
+			shader,
+			blending
+		}
+	}
+}
+
+impl Material for Particle {
+	fn shader(&self) -> &FragmentShader {
+		&self.shader
+	}
+
+	fn is_blended(&self) -> bool {
+		true
+	}
+
+	fn blending(&self) -> Blending {
+		self.blending
+	}
+}
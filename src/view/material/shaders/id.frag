@@ -0,0 +1,10 @@
+#version 450
+layout(push_constant) uniform Object {
+	layout(offset = 128) uint id;
+} pc;
+
+layout(location = 0) out uint id;
+
+void main() {
+	id = pc.id;
+}
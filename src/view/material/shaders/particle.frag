@@ -0,0 +1,10 @@
+#version 450
+layout(location = 0) in vec4 color;
+layout(location = 1) in vec2 uv;
+
+layout(location = 0) out vec4 out_color;
+
+void main() {
+	float falloff = 1.0 - smoothstep(0.25, 0.5, length(uv));
+	out_color = vec4(color.rgb, color.a * falloff);
+}
@@ -1,24 +1,136 @@
-use glam::Mat4;
-use magma::command;
+use std::sync::Arc;
+use magma::{
+	command,
+	framebuffer::RenderPass
+};
 use crate::render;
+use geometry::projection::CameraProjection;
 
 pub mod geometry;
 pub mod material;
 pub mod object;
+pub mod instanced;
+pub mod lines;
+pub mod points;
+pub mod sprite;
+pub mod particles;
+pub mod group;
 
 pub use geometry::Geometry;
 pub use material::Material;
 pub use object::Object;
+pub use instanced::Instanced;
+pub use lines::Lines;
+pub use points::Points;
+pub use sprite::Sprite;
+pub use particles::Particles;
+pub use group::Group;
 
 /// Object graphical representation.
 pub enum View {
-	Object(Object)
+	Object(Object),
+
+	/// Many instances of the same object, drawn at once.
+	Instanced(Instanced),
+
+	/// Line segments.
+	Lines(Lines),
+
+	/// Point cloud.
+	Points(Points),
+
+	/// Camera-facing quad.
+	Sprite(Sprite),
+
+	/// GPU simulated particles.
+	Particles(Particles),
+
+	/// Composite view.
+	Group(Group)
 }
 
 impl View {
-	pub fn draw<C: render::Context, B: command::Buffer>(&self, context: &C, commands: &mut command::buffer::Recorder<B>, projection: &Mat4) {
+	/// Underlying object, if any.
+	///
+	/// Returns `None` for groups.
+	pub fn object(&self) -> Option<&Object> {
+		match self {
+			View::Object(obj) => Some(obj),
+			View::Instanced(instanced) => Some(instanced.object()),
+			View::Lines(lines) => Some(lines.object()),
+			View::Points(points) => Some(points.object()),
+			View::Sprite(sprite) => Some(sprite.object()),
+			View::Particles(particles) => Some(particles.object()),
+			View::Group(_) => None
+		}
+	}
+
+	/// Checks if this view is blended with what is behind it.
+	///
+	/// A group is blended if any of its sub-views is blended.
+	pub fn is_blended(&self) -> bool {
+		match self {
+			View::Group(group) => group.iter().any(|(_, view)| view.is_blended()),
+			view => view.object().unwrap().is_blended()
+		}
+	}
+
+	/// Checks if some geometry buffers requested by this view are still being loaded.
+	pub fn is_loading(&self) -> bool {
 		match self {
-			View::Object(obj) => obj.draw(context, commands, projection)
+			View::Group(group) => group.iter().any(|(_, view)| view.is_loading()),
+			view => view.object().unwrap().geometry().is_loading()
 		}
 	}
-}
\ No newline at end of file
+
+	/// Identifiers of the graphics pipeline and geometry buffers used to draw this view.
+	///
+	/// Used to sort draws so that views sharing the same state are drawn together.
+	/// The state of a group is the state of its first sub-view.
+	pub fn state<T: render::Target>(&self, target: &T) -> (usize, usize) {
+		match self {
+			View::Group(group) => match group.iter().next() {
+				Some((_, view)) => view.state(target),
+				None => (0, 0)
+			},
+			view => {
+				let object = view.object().unwrap();
+				let pipeline = Arc::as_ptr(&*object.pipeline(target)) as usize;
+				(pipeline, object.geometry().id())
+			}
+		}
+	}
+
+	pub fn draw<C: render::Context, B: command::Buffer>(&self, context: &C, commands: &mut command::buffer::Recorder<B>, projection: &CameraProjection) {
+		match self {
+			View::Object(obj) => obj.draw(context, commands, projection),
+			View::Instanced(instanced) => instanced.draw(context, commands, projection),
+			View::Lines(lines) => lines.draw(context, commands, projection),
+			View::Points(points) => points.draw(context, commands, projection),
+			View::Sprite(sprite) => sprite.draw(context, commands, projection),
+			View::Particles(particles) => particles.draw(context, commands, projection),
+			View::Group(group) => group.draw(context, commands, projection)
+		}
+	}
+
+	/// Draw the given id, used for picking.
+	///
+	/// Particles are not pickable.
+	/// See [`Object::draw_id`].
+	pub fn draw_id<C: render::Context, B: command::Buffer>(
+		&self,
+		context: &C,
+		commands: &mut command::buffer::Recorder<B>,
+		projection: &CameraProjection,
+		render_pass: &Arc<RenderPass>,
+		shader: &material::FragmentShader,
+		id: u32
+	) {
+		match self {
+			View::Instanced(instanced) => instanced.draw_id(context, commands, projection, render_pass, shader, id),
+			View::Group(group) => group.draw_id(context, commands, projection, render_pass, shader, id),
+			View::Particles(_) => (),
+			view => view.object().unwrap().draw_id(context, commands, projection, render_pass, shader, id, None)
+		}
+	}
+}
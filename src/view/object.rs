@@ -4,27 +4,94 @@ use glam::{
 	Mat4
 };
 use magma::{
+	Device,
 	Format,
+	framebuffer::RenderPass,
 	pipeline,
 	command,
-	mem,
-	device
+	mem::{
+		self,
+		buffer
+	}
 };
 use parking_lot::{
 	Mutex,
 	MutexGuard,
 	MappedMutexGuard
 };
-use crate::{
-	render,
-	sync::Loader
-};
+use crate::render;
 use super::{
-	geometry,
+	geometry::{
+		self,
+		projection::{
+			CameraProjection,
+			PROJECTION_PUSH_CONSTANT_SIZE
+		}
+	},
 	Geometry,
-	Material
+	Material,
+	material::{
+		Blending,
+		FragmentShader
+	}
 };
 
+/// Offset of the object id push constant, after the projection matrices.
+///
+/// Beyond the 128 bytes guaranteed by Vulkan: the device limit is checked by [`IdPass::new`](crate::render::picking::IdPass::new).
+pub const ID_PUSH_CONSTANT_OFFSET: u32 = PROJECTION_PUSH_CONSTANT_SIZE;
+
+/// Primitive topology used to draw an object.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Primitive {
+	/// Triangle list.
+	Triangles,
+
+	/// Triangle strip.
+	TriangleStrip,
+
+	/// Line list, with the given line width.
+	///
+	/// Widths other than `1.0` require the `wideLines` device feature.
+	Lines(f32),
+
+	/// Line strip, with the given line width.
+	///
+	/// Widths other than `1.0` require the `wideLines` device feature.
+	LineStrip(f32),
+
+	/// Point list.
+	///
+	/// The size of the points is given by the projection shader (`gl_PointSize`).
+	Points
+}
+
+impl Primitive {
+	fn topology(&self) -> pipeline::input_assembly::Topology {
+		use pipeline::input_assembly::Topology;
+		match self {
+			Primitive::Triangles => Topology::TriangleList,
+			Primitive::TriangleStrip => Topology::TriangleStrip,
+			Primitive::Lines(_) => Topology::LineList,
+			Primitive::LineStrip(_) => Topology::LineStrip,
+			Primitive::Points => Topology::PointList
+		}
+	}
+
+	fn line_width(&self) -> f32 {
+		match self {
+			Primitive::Lines(width) | Primitive::LineStrip(width) => *width,
+			_ => 1.0
+		}
+	}
+}
+
+impl Default for Primitive {
+	fn default() -> Self {
+		Primitive::Triangles
+	}
+}
+
 pub struct Object {
 	/// Geometry.
 	geometry: Geometry,
@@ -34,24 +101,89 @@ pub struct Object {
 
 	/// Material.
 	material: Arc<dyn Material>,
+
+	/// Primitive topology.
+	primitive: Primitive,
+
+	/// Does the pipeline expects per-instance transformations.
+	instanced: bool,
 	
 	/// Graphics pipeline.
-	pipeline: Mutex<Option<Arc<pipeline::Graphics>>>
+	pipeline: Mutex<Option<Arc<pipeline::Graphics>>>,
+
+	/// Graphics pipeline used to draw the object id.
+	id_pipeline: Mutex<Option<Arc<pipeline::Graphics>>>
 }
 
 impl Object {
+	/// Create a new object made of triangles.
+	pub fn new(geometry: Geometry, projection: Arc<dyn geometry::Projection>, material: Arc<dyn Material>) -> Self {
+		Self::with_primitive(geometry, projection, material, Primitive::Triangles)
+	}
+
+	/// Create a new object drawn with the given primitive topology.
+	pub fn with_primitive(geometry: Geometry, projection: Arc<dyn geometry::Projection>, material: Arc<dyn Material>, primitive: Primitive) -> Self {
+		Self {
+			geometry,
+			projection,
+			material,
+			primitive,
+			instanced: false,
+			pipeline: Mutex::new(None),
+			id_pipeline: Mutex::new(None)
+		}
+	}
+
+	/// Create a new object that is meant to be drawn many times at once.
+	///
+	/// The graphics pipeline of such object reads a per-instance transformation
+	/// matrix from the vertex buffer bound at binding `1` (locations `1` to `4`).
+	pub fn new_instanced(geometry: Geometry, projection: Arc<dyn geometry::Projection>, material: Arc<dyn Material>) -> Self {
+		Self {
+			geometry,
+			projection,
+			material,
+			primitive: Primitive::Triangles,
+			instanced: true,
+			pipeline: Mutex::new(None),
+			id_pipeline: Mutex::new(None)
+		}
+	}
+
+	/// Object geometry.
+	pub fn geometry(&self) -> &Geometry {
+		&self.geometry
+	}
+
+	/// Primitive topology.
+	pub fn primitive(&self) -> Primitive {
+		self.primitive
+	}
+
+	/// Checks if this object is blended with what is behind it.
+	pub fn is_blended(&self) -> bool {
+		self.material.is_blended()
+	}
+
+	/// Checks if this object is meant to be drawn with instancing.
+	pub fn is_instanced(&self) -> bool {
+		self.instanced
+	}
+
 	pub fn draw<C: render::Context, B: command::Buffer>(
 		&self,
 		context: &C,
 		commands: &mut command::buffer::Recorder<B>,
-		projection: &Mat4
+		projection: &CameraProjection
 	) {
 		if let Some(vertex_buffer) = self.geometry.vertex_buffer(context.loader(), context.graphics_queue().into()) {
 			if let Some(index_buffer) = self.geometry.index_buffer(0, context.loader(), context.graphics_queue().into()) {
 				let mut vertex_buffers = mem::Buffers::new();
 				vertex_buffers.push(vertex_buffer.clone());
 
-				commands.bind_graphics_pipeline(&self.pipeline(context.target()));
+				let pipeline = self.pipeline(context.target());
+				commands.bind_graphics_pipeline(&pipeline);
+				commands.push_constants(pipeline.layout(), pipeline::shader::Stage::Vertex, 0, projection.as_bytes());
 				commands.bind_vertex_buffers(0, vertex_buffers, &[0]);
 				commands.bind_index_buffer(index_buffer.clone(), 0);
 				commands.draw_indexed(index_buffer.len() as u32, 1, 0, 0, 0);
@@ -59,6 +191,75 @@ impl Object {
 		}
 	}
 
+	/// Draw `instance_count` instances of this object at once.
+	///
+	/// The given instance buffer must contain at least `instance_count` instances
+	/// of 64 bytes each, usually transformation matrices.
+	/// This object must have been created with [`Object::new_instanced`].
+	pub fn draw_instanced<C: render::Context, B: command::Buffer, I: 'static + magma::Buffer>(
+		&self,
+		context: &C,
+		commands: &mut command::buffer::Recorder<B>,
+		projection: &CameraProjection,
+		instance_buffer: &Arc<I>,
+		instance_count: u32
+	) {
+		debug_assert!(self.instanced);
+
+		if let Some(vertex_buffer) = self.geometry.vertex_buffer(context.loader(), context.graphics_queue().into()) {
+			if let Some(index_buffer) = self.geometry.index_buffer(0, context.loader(), context.graphics_queue().into()) {
+				let mut vertex_buffers = mem::Buffers::new();
+				vertex_buffers.push(vertex_buffer.clone());
+				vertex_buffers.push(instance_buffer.clone());
+
+				let pipeline = self.pipeline(context.target());
+				commands.bind_graphics_pipeline(&pipeline);
+				commands.push_constants(pipeline.layout(), pipeline::shader::Stage::Vertex, 0, projection.as_bytes());
+				commands.bind_vertex_buffers(0, vertex_buffers, &[0, 0]);
+				commands.bind_index_buffer(index_buffer.clone(), 0);
+				commands.draw_indexed(index_buffer.len() as u32, instance_count, 0, 0, 0);
+			}
+		}
+	}
+
+	/// Draw the given object id with the given picking render pass and shader.
+	///
+	/// Instanced objects must be given their instance buffer and instance count.
+	/// Every instance is drawn with the same id.
+	pub fn draw_id<C: render::Context, B: command::Buffer>(
+		&self,
+		context: &C,
+		commands: &mut command::buffer::Recorder<B>,
+		projection: &CameraProjection,
+		render_pass: &Arc<RenderPass>,
+		shader: &FragmentShader,
+		id: u32,
+		instances: Option<(&Arc<buffer::Typed<Mat4>>, u32)>
+	) {
+		if let Some(vertex_buffer) = self.geometry.vertex_buffer(context.loader(), context.graphics_queue().into()) {
+			if let Some(index_buffer) = self.geometry.index_buffer(0, context.loader(), context.graphics_queue().into()) {
+				let mut vertex_buffers = mem::Buffers::new();
+				vertex_buffers.push(vertex_buffer.clone());
+
+				let (offsets, instance_count): (&[u64], u32) = match instances {
+					Some((instance_buffer, instance_count)) => {
+						vertex_buffers.push(instance_buffer.clone());
+						(&[0, 0], instance_count)
+					},
+					None => (&[0], 1)
+				};
+
+				let pipeline = self.id_pipeline(context.target().device(), render_pass, shader);
+				commands.bind_graphics_pipeline(&pipeline);
+				commands.push_constants(pipeline.layout(), pipeline::shader::Stage::Vertex, 0, projection.as_bytes());
+				commands.push_constants(pipeline.layout(), pipeline::shader::Stage::Fragment, ID_PUSH_CONSTANT_OFFSET, &id.to_ne_bytes());
+				commands.bind_vertex_buffers(0, vertex_buffers, offsets);
+				commands.bind_index_buffer(index_buffer.clone(), 0);
+				commands.draw_indexed(index_buffer.len() as u32, instance_count, 0, 0, 0);
+			}
+		}
+	}
+
 	/// Build a graphics pipeline for this object.
 	///
 	/// TODO share graphics pipelines.
@@ -68,15 +269,58 @@ impl Object {
 		pipeline_guard: &mut MutexGuard<Option<Arc<pipeline::Graphics>>>
 	) {
 		use pipeline::{
-			InputAssembly,
-			input_assembly,
-			Viewport,
-			Scissor,
 			ColorBlend,
 			color_blend::{
 				self,
 				BlendFactor
-			},
+			}
+		};
+
+		let dst_color_factor = match self.material.blending() {
+			Blending::Alpha => BlendFactor::OneMinusSourceAlpha,
+			Blending::Additive => BlendFactor::One
+		};
+
+		let color_blend = ColorBlend::new(None, [0.0, 0.0, 0.0, 0.0]).with_attachment(color_blend::Attachment::new(
+			Some(color_blend::AttachmentBlend::new(
+				BlendFactor::SourceAlpha,
+				dst_color_factor,
+				color_blend::Operation::Add,
+				BlendFactor::One,
+				BlendFactor::Zero,
+				color_blend::Operation::Add
+			)),
+			color_blend::ColorComponents::rgba()
+		));
+
+		let pipeline = self.build_pipeline(
+			target.device(),
+			target.render_pass(),
+			self.material.shader(),
+			color_blend,
+			&[]
+		);
+
+		// Build.
+		pipeline_guard.replace(Arc::new(pipeline));
+	}
+
+	/// Build a graphics pipeline for this object, with the given fragment shader.
+	///
+	/// The projection push constants are declared for the vertex stage,
+	/// in addition to the given push constant ranges.
+	fn build_pipeline(
+		&self,
+		device: &Arc<Device>,
+		render_pass: &Arc<RenderPass>,
+		fragment_shader: &FragmentShader,
+		color_blend: pipeline::ColorBlend,
+		push_constant_ranges: &[pipeline::layout::PushConstantRange]
+	) -> pipeline::Graphics {
+		use pipeline::{
+			InputAssembly,
+			Viewport,
+			Scissor,
 			DynamicState
 		};
 
@@ -84,7 +328,7 @@ impl Object {
 			pipeline::stage::Vertex::new(
 				self.projection.shader().entry_point(),
 				pipeline::stage::Fragment::new(
-					self.material.shader().entry_point()
+					fragment_shader.entry_point()
 				)
 			)
 		};
@@ -102,24 +346,45 @@ impl Object {
 			0 // offset
 		));
 
+		if self.instanced {
+			// Per-instance transformation matrix, one column per location.
+			vertex_input.add_binding(pipeline::vertex_input::Binding::new(
+				1,
+				std::mem::size_of::<Mat4>() as u32,
+				pipeline::vertex_input::Rate::Instance
+			));
+
+			for column in 0..4 {
+				vertex_input.add_attribute(pipeline::vertex_input::Attribute::new(
+					1 + column, // location
+					1, // binding
+					Format::R32G32B32A32Sfloat,
+					column * std::mem::size_of::<glam::Vec4>() as u32 // offset
+				));
+			}
+		}
+
 		// let set_layouts = &[
 		// 	pipeline::layout::Set::new(target.device(), &[]).expect("unable to create set")
 		// ];
 		let set_layouts = &[]; // TODO
 
-		let push_constant_ranges = &[]; // TODO
+		let mut ranges = vec![
+			pipeline::layout::PushConstantRange::new(pipeline::shader::Stage::Vertex, 0, PROJECTION_PUSH_CONSTANT_SIZE)
+		];
+		ranges.extend_from_slice(push_constant_ranges);
 
 		let layout = Arc::new(pipeline::Layout::new(
-			target.device(),
+			device,
 			set_layouts,
-			push_constant_ranges
+			&ranges
 		).expect("unable to create layout"));
 
-		let pipeline = pipeline::Graphics::new(
-			target.device(),
+		pipeline::Graphics::new(
+			device,
 			&stages,
 			vertex_input,
-			InputAssembly::new(input_assembly::Topology::TriangleList, false),
+			InputAssembly::new(self.primitive.topology(), false),
 			None, // no tesselation
 			[Viewport::default()], [Scissor::default()],
 			pipeline::Rasterization::new(
@@ -129,29 +394,16 @@ impl Object {
 				pipeline::rasterization::CullMode::Back,
 				pipeline::rasterization::FrontFace::Clockwise,
 				None,
-				1.0
+				self.primitive.line_width()
 			),
 			pipeline::Multisample::default(), // no multisampling
 			None,
 			None,
-			ColorBlend::new(None, [0.0, 0.0, 0.0, 0.0]).with_attachment(color_blend::Attachment::new(
-				Some(color_blend::AttachmentBlend::new(
-					BlendFactor::SourceAlpha,
-					BlendFactor::OneMinusSourceAlpha,
-					color_blend::Operation::Add,
-					BlendFactor::One,
-					BlendFactor::Zero,
-					color_blend::Operation::Add
-				)),
-				color_blend::ColorComponents::rgba()
-			)),
+			color_blend,
 			&layout,
-			target.render_pass().subpass(0).unwrap(),
+			render_pass.subpass(0).unwrap(),
 			(DynamicState::Viewport, DynamicState::Scissor)
-		).expect("unable to build pipeline");
-
-		// Build.
-		pipeline_guard.replace(Arc::new(pipeline));
+		).expect("unable to build pipeline")
 	}
 
 	/// Drawing pipeline for the given render target.
@@ -167,4 +419,31 @@ impl Object {
 
 		MutexGuard::map(guard, |p| p.as_mut().unwrap())
 	}
-}
\ No newline at end of file
+
+	/// Pipeline drawing the object id in the given picking render pass.
+	pub fn id_pipeline<'a>(&'a self, device: &Arc<Device>, render_pass: &Arc<RenderPass>, shader: &FragmentShader) -> MappedMutexGuard<'a, Arc<pipeline::Graphics>> {
+		let mut guard = self.id_pipeline.lock();
+
+		if guard.is_none() {
+			use pipeline::{
+				ColorBlend,
+				color_blend
+			};
+
+			// Ids are written as is, without blending.
+			let color_blend = ColorBlend::new(None, [0.0, 0.0, 0.0, 0.0]).with_attachment(color_blend::Attachment::new(
+				None,
+				color_blend::ColorComponents::rgba()
+			));
+
+			let push_constant_ranges = &[
+				pipeline::layout::PushConstantRange::new(pipeline::shader::Stage::Fragment, ID_PUSH_CONSTANT_OFFSET, std::mem::size_of::<u32>() as u32)
+			];
+
+			let pipeline = self.build_pipeline(device, render_pass, shader, color_blend, push_constant_ranges);
+			guard.replace(Arc::new(pipeline));
+		}
+
+		MutexGuard::map(guard, |p| p.as_mut().unwrap())
+	}
+}
@@ -0,0 +1,389 @@
+use std::{
+	sync::{
+		Arc,
+		atomic::{
+			self,
+			AtomicUsize
+		}
+	},
+	ops::Range
+};
+use glam::{
+	Vec3,
+	Vec4
+};
+use magma::{
+	Device,
+	command,
+	mem::buffer,
+	sync::SharingQueues
+};
+use parking_lot::Mutex;
+use crate::{
+	render,
+	compute::{
+		self,
+		Compute,
+		ComputeShader
+	},
+	sync::{
+		Loader,
+		loader::Loading
+	}
+};
+use super::{
+	Object,
+	geometry::projection::CameraProjection
+};
+
+/// Size of the simulation push constants (time step and current time).
+pub const SIMULATION_PUSH_CONSTANTS_SIZE: u32 = 8;
+
+/// Number of particles simulated by each compute work group.
+const WORK_GROUP_SIZE: u32 = 64;
+
+/// Value sampled over the lifetime of a particle.
+///
+/// The keys are evenly spaced over the particle lifetime, and linearly interpolated.
+#[derive(Clone, Copy, Debug)]
+pub struct Curve<T> {
+	pub keys: [T; 4]
+}
+
+impl<T: Copy> Curve<T> {
+	pub fn new(keys: [T; 4]) -> Self {
+		Self {
+			keys
+		}
+	}
+
+	/// Constant curve.
+	pub fn constant(value: T) -> Self {
+		Self::new([value; 4])
+	}
+}
+
+impl Curve<f32> {
+	/// Linear curve from `start` to `end`.
+	pub fn linear(start: f32, end: f32) -> Self {
+		let key = |i: usize| start + (end - start) * i as f32 / 3.0;
+		Self::new([key(0), key(1), key(2), key(3)])
+	}
+}
+
+impl Curve<Vec4> {
+	/// Linear curve from `start` to `end`.
+	pub fn linear(start: Vec4, end: Vec4) -> Self {
+		let key = |i: usize| start.lerp(end, i as f32 / 3.0);
+		Self::new([key(0), key(1), key(2), key(3)])
+	}
+}
+
+/// Particle emitter.
+///
+/// Particles are emitted from the origin of the view, at a constant rate.
+/// Each particle slot is respawned every `capacity / rate` seconds,
+/// so the capacity must be at least `rate * lifetime.end` for particles to live their whole lifetime.
+#[derive(Clone, Debug)]
+pub struct Emitter {
+	/// Maximum number of particles.
+	pub capacity: u32,
+
+	/// Emitted particles per second.
+	pub rate: f32,
+
+	/// Lifetime range of a particle, in seconds.
+	pub lifetime: Range<f32>,
+
+	/// Mean emission direction.
+	pub direction: Vec3,
+
+	/// Angle between the emission direction and the velocity of emitted particles, in radians.
+	pub spread: f32,
+
+	/// Initial speed range.
+	pub speed: Range<f32>,
+
+	/// Constant acceleration, such as gravity or wind.
+	pub gravity: Vec3,
+
+	/// Velocity damping factor.
+	pub drag: f32,
+
+	/// Color over lifetime.
+	pub color: Curve<Vec4>,
+
+	/// Size over lifetime.
+	pub size: Curve<f32>
+}
+
+impl Emitter {
+	/// Respawn period of each particle slot.
+	pub fn period(&self) -> f32 {
+		self.capacity as f32 / self.rate
+	}
+
+	/// Initial particles state.
+	///
+	/// Particles are not spawned yet, and are scheduled to be spawned one after the other.
+	fn initial_state(&self) -> Vec<Particle> {
+		let period = self.period();
+		(0..self.capacity).map(|i| {
+			// Each particle is spawned once its age reaches `period`.
+			let age = period - i as f32 / self.rate;
+			Particle {
+				position: [0.0, 0.0, 0.0, age],
+				velocity: [0.0; 4],
+				color: [0.0; 4],
+				size: [0.0, i as f32 / self.capacity as f32, 0.0, 0.0]
+			}
+		}).collect()
+	}
+
+	fn params(&self) -> EmitterParams {
+		EmitterParams {
+			direction: [self.direction.x, self.direction.y, self.direction.z, self.spread],
+			ranges: [self.speed.start, self.speed.end, self.lifetime.start, self.lifetime.end],
+			forces: [self.gravity.x, self.gravity.y, self.gravity.z, self.drag],
+			emission: [self.period(), self.capacity as f32, 0.0, 0.0],
+			colors: [
+				self.color.keys[0].into(),
+				self.color.keys[1].into(),
+				self.color.keys[2].into(),
+				self.color.keys[3].into()
+			],
+			sizes: self.size.keys
+		}
+	}
+}
+
+impl Default for Emitter {
+	fn default() -> Self {
+		Self {
+			capacity: 1024,
+			rate: 256.0,
+			lifetime: 1.0..2.0,
+			direction: Vec3::unit_y(),
+			spread: 0.3,
+			speed: 1.0..2.0,
+			gravity: Vec3::zero(),
+			drag: 0.0,
+			color: Curve::constant(Vec4::one()),
+			size: Curve::constant(0.1)
+		}
+	}
+}
+
+/// Particle state, as stored in GPU memory.
+///
+/// Has the size of a transformation matrix, so that the state buffer
+/// can be used as the instance buffer of an instanced object.
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct Particle {
+	/// Position and age.
+	position: [f32; 4],
+
+	/// Velocity and lifetime.
+	velocity: [f32; 4],
+
+	/// Current color.
+	color: [f32; 4],
+
+	/// Current size and random seed.
+	size: [f32; 4]
+}
+
+/// Emitter parameters, as read by the simulation shader.
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct EmitterParams {
+	direction: [f32; 4],
+	ranges: [f32; 4],
+	forces: [f32; 4],
+	emission: [f32; 4],
+	colors: [[f32; 4]; 4],
+	sizes: [f32; 4]
+}
+
+/// Simulation step push constants.
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct Step {
+	dt: f32,
+	time: f32
+}
+
+/// Simulation state.
+struct Simulation {
+	/// Simulated time.
+	time: f32,
+
+	/// Time elapsed since the last dispatched step.
+	elapsed: f32,
+
+	/// Index of the state buffer written by the last completed step.
+	current: usize,
+
+	/// Last dispatched step.
+	pending: Option<Loading<()>>
+}
+
+/// GPU particle system.
+///
+/// Particles are simulated by a compute shader (`shaders/simulate.comp`),
+/// with their state kept in GPU storage buffers.
+/// They are then drawn as instances of a camera-facing quad,
+/// with the [`Particle`](super::geometry::projection::Particle) projection
+/// and [`Particle`](super::material::Particle) material.
+///
+/// The state is kept in two buffers: each step reads the state drawn by the graphics queue,
+/// and writes the other buffer, drawn once the step is done.
+/// A step is only dispatched once every frame drawing the buffer it writes has been executed,
+/// so that the compute queue never writes a buffer read by the graphics queue.
+pub struct Particles {
+	/// Quad object.
+	object: Object,
+
+	/// Emitter.
+	emitter: Emitter,
+
+	/// Simulation pipeline.
+	pipeline: Arc<compute::Pipeline>,
+
+	/// Particles state buffers.
+	states: [Loading<buffer::Typed<Particle>>; 2],
+
+	/// Emitter parameters.
+	params: Loading<buffer::Typed<EmitterParams>>,
+
+	/// Number of frames drawing each state buffer that have not been executed yet.
+	in_flight: [Arc<AtomicUsize>; 2],
+
+	simulation: Mutex<Simulation>
+}
+
+impl Particles {
+	/// Create a new particle system.
+	///
+	/// The given object must have been created with [`Object::new_instanced`],
+	/// and the simulation pipeline with [`Particles::simulation_pipeline`].
+	/// The particles buffers are shared between the given queues,
+	/// that must include the graphics queue and the compute queue.
+	pub fn new(object: Object, emitter: Emitter, pipeline: &Arc<compute::Pipeline>, loader: &Loader, sharing_queues: SharingQueues) -> Self {
+		assert!(object.is_instanced());
+		assert!(emitter.capacity > 0 && emitter.rate > 0.0);
+
+		let state = || loader.load(
+			emitter.initial_state(),
+			buffer::Usage::StorageBuffer | buffer::Usage::VertexBuffer,
+			sharing_queues.clone()
+		);
+		let states = [state(), state()];
+
+		let params = loader.load(
+			vec![emitter.params()],
+			buffer::Usage::StorageBuffer,
+			sharing_queues
+		);
+
+		Self {
+			object,
+			emitter,
+			pipeline: pipeline.clone(),
+			states,
+			params,
+			in_flight: [Arc::new(AtomicUsize::new(0)), Arc::new(AtomicUsize::new(0))],
+			simulation: Mutex::new(Simulation {
+				time: 0.0,
+				elapsed: 0.0,
+				current: 0,
+				pending: None
+			})
+		}
+	}
+
+	/// Create the simulation pipeline from the compiled `shaders/simulate.comp` shader.
+	///
+	/// The pipeline can be shared by every particle system.
+	pub fn simulation_pipeline(device: &Arc<Device>, shader: &ComputeShader) -> compute::Pipeline {
+		compute::Pipeline::new(device, shader, 3, SIMULATION_PUSH_CONSTANTS_SIZE)
+	}
+
+	/// Quad object.
+	pub fn object(&self) -> &Object {
+		&self.object
+	}
+
+	/// Emitter.
+	pub fn emitter(&self) -> &Emitter {
+		&self.emitter
+	}
+
+	/// Advance the simulation by `dt` seconds.
+	///
+	/// Dispatches a simulation step on the given compute dispatcher.
+	/// If the previous step is not done yet, if some frames drawing the buffer written by the next step
+	/// have not been executed yet, or if the particles buffers are not loaded,
+	/// the elapsed time is accumulated for the next step.
+	/// Returns `true` if a step has been dispatched.
+	pub fn simulate(&self, compute: &Compute, dt: f32) -> bool {
+		let mut simulation = self.simulation.lock();
+		simulation.elapsed += dt;
+
+		if let Some(pending) = &simulation.pending {
+			if pending.get().is_none() {
+				return false
+			}
+
+			// The step is done: draw its state from now on.
+			simulation.pending = None;
+			simulation.current = 1 - simulation.current
+		}
+
+		let current = simulation.current;
+		if self.in_flight[1 - current].load(atomic::Ordering::Acquire) > 0 {
+			// The previous state may still be read by a frame in flight.
+			return false
+		}
+
+		match (self.states[current].get(), self.states[1 - current].get(), self.params.get()) {
+			(Some(src), Some(dst), Some(params)) => {
+				let step = Step {
+					dt: simulation.elapsed,
+					time: simulation.time
+				};
+
+				let groups = (self.emitter.capacity + WORK_GROUP_SIZE - 1) / WORK_GROUP_SIZE;
+				let job = compute::Job::new(&self.pipeline, (groups, 1, 1))
+					.with_buffer(src.clone())
+					.with_buffer(dst.clone())
+					.with_buffer(params.clone())
+					.with_push_constants(&step);
+
+				simulation.pending = Some(compute.dispatch(job));
+				simulation.time += simulation.elapsed;
+				simulation.elapsed = 0.0;
+				true
+			},
+			_ => false
+		}
+	}
+
+	/// Draw the state of the last completed step.
+	///
+	/// The state buffer is not written again until the frame has been executed
+	/// (see [`render::Context::on_frame_complete`]).
+	pub fn draw<C: render::Context, B: command::Buffer>(&self, context: &C, commands: &mut command::buffer::Recorder<B>, projection: &CameraProjection) {
+		let simulation = self.simulation.lock();
+		if let Some(state) = self.states[simulation.current].get() {
+			self.object.draw_instanced(context, commands, projection, state, self.emitter.capacity);
+
+			let in_flight = self.in_flight[simulation.current].clone();
+			in_flight.fetch_add(1, atomic::Ordering::AcqRel);
+			context.on_frame_complete(Box::new(move || {
+				in_flight.fetch_sub(1, atomic::Ordering::AcqRel);
+			}))
+		}
+	}
+}
@@ -0,0 +1,41 @@
+use std::sync::Arc;
+use magma::command;
+use crate::render;
+use super::{
+	geometry::{
+		self,
+		projection::CameraProjection
+	},
+	Geometry,
+	Material,
+	Object,
+	object::Primitive
+};
+
+/// Point cloud.
+///
+/// The size of each point is given by the projection shader,
+/// through the `gl_PointSize` output.
+pub struct Points {
+	object: Object
+}
+
+impl Points {
+	/// Create a new point cloud.
+	///
+	/// Only the geometry vertices referenced by its indices are drawn.
+	pub fn new(geometry: Geometry, projection: Arc<dyn geometry::Projection>, material: Arc<dyn Material>) -> Self {
+		Self {
+			object: Object::with_primitive(geometry, projection, material, Primitive::Points)
+		}
+	}
+
+	/// Underlying object.
+	pub fn object(&self) -> &Object {
+		&self.object
+	}
+
+	pub fn draw<C: render::Context, B: command::Buffer>(&self, context: &C, commands: &mut command::buffer::Recorder<B>, projection: &CameraProjection) {
+		self.object.draw(context, commands, projection)
+	}
+}
@@ -0,0 +1,39 @@
+use std::sync::Arc;
+use magma::command;
+use crate::render;
+use super::{
+	geometry::{
+		self,
+		projection::CameraProjection
+	},
+	Geometry,
+	Material,
+	Object
+};
+
+/// Camera-facing quad.
+///
+/// The quad geometry is given in view space, centered on the sprite origin.
+/// It is meant to be used with the [`Billboard`](geometry::projection::Billboard) projection,
+/// that keeps the quad facing the camera whatever the sprite orientation.
+pub struct Sprite {
+	object: Object
+}
+
+impl Sprite {
+	/// Create a new sprite.
+	pub fn new(quad: Geometry, projection: Arc<dyn geometry::Projection>, material: Arc<dyn Material>) -> Self {
+		Self {
+			object: Object::new(quad, projection, material)
+		}
+	}
+
+	/// Underlying object.
+	pub fn object(&self) -> &Object {
+		&self.object
+	}
+
+	pub fn draw<C: render::Context, B: command::Buffer>(&self, context: &C, commands: &mut command::buffer::Recorder<B>, projection: &CameraProjection) {
+		self.object.draw(context, commands, projection)
+	}
+}
@@ -1,53 +1,107 @@
 use std::sync::Arc;
 use magma::sync::{
-	fence,
-	Fence
+	Fence,
+	future::SignalFence,
+	semaphore
 };
 use crossbeam_queue::SegQueue;
 
-type Fences<T> = SegQueue<(Box<dyn Fence>, Box<dyn Send + FnOnce(&mut T) -> ()>)>;
+type Callback<T> = Box<dyn Send + FnOnce(&mut T) -> ()>;
 
+type Pending<T> = SegQueue<(Wait, Callback<T>)>;
+
+/// What to wait for.
+enum Wait {
+	Fence(Box<dyn Send + Fence>),
+	Future(Box<dyn Send + SignalFence>),
+	Timeline(Arc<semaphore::Timeline>, u64)
+}
+
+impl Wait {
+	fn is_signaled(&self) -> bool {
+		match self {
+			Wait::Fence(fence) => fence.is_signaled().expect("fence error"),
+			Wait::Future(future) => future.is_signaled().expect("fence error"),
+			Wait::Timeline(semaphore, value) => semaphore.value().expect("semaphore error") >= *value
+		}
+	}
+}
+
+/// GPU synchronization callbacks.
+///
+/// Registers callbacks that are called on the state by the associated [`Worker`],
+/// during the apply phase following the signal of a fence, future or timeline semaphore.
 pub struct Waiter<T> {
-	fences: Arc<Fences<T>>
+	pending: Arc<Pending<T>>
+}
+
+impl<T> Clone for Waiter<T> {
+	fn clone(&self) -> Self {
+		Waiter {
+			pending: self.pending.clone()
+		}
+	}
 }
 
 impl<T> Waiter<T> {
 	pub fn new() -> (Waiter<T>, Worker<T>) {
-		let fences = Arc::new(Fences::new());
+		let pending = Arc::new(Pending::new());
 
 		let waiter = Waiter {
-			fences: fences.clone()
+			pending: pending.clone()
 		};
 
 		let worker = Worker {
-			pending_fences: fences,
-			fences: Vec::new(),
+			pending,
+			waiting: Vec::new(),
 			signaled: Vec::new()
 		};
 
 		(waiter, worker)
 	}
+
+	fn push<F: 'static + Send + FnOnce(&mut T)>(&self, wait: Wait, f: F) {
+		self.pending.push((wait, Box::new(f)))
+	}
+
+	/// Call `f` once the given fence is signaled.
+	pub fn fence<E: 'static + Send + Fence, F: 'static + Send + FnOnce(&mut T)>(&self, fence: E, f: F) {
+		self.push(Wait::Fence(Box::new(fence)), f)
+	}
+
+	/// Call `f` once the given submission future is signaled.
+	///
+	/// The future (and the resources it holds) is dropped after `f` is called.
+	pub fn future<S: 'static + Send + SignalFence, F: 'static + Send + FnOnce(&mut T)>(&self, future: S, f: F) {
+		self.push(Wait::Future(Box::new(future)), f)
+	}
+
+	/// Call `f` once the given timeline semaphore reaches the given value.
+	pub fn timeline<F: 'static + Send + FnOnce(&mut T)>(&self, semaphore: &Arc<semaphore::Timeline>, value: u64, f: F) {
+		self.push(Wait::Timeline(semaphore.clone(), value), f)
+	}
 }
 
 /// Fence synchronization.
 pub struct Worker<T> {
-	pending_fences: Arc<Fences<T>>,
-	fences: Vec<(Box<dyn Fence>, Box<dyn Send + FnOnce(&mut T) -> ()>)>,
-	signaled: Vec<Box<dyn Send + FnOnce(&mut T) -> ()>>
+	pending: Arc<Pending<T>>,
+	waiting: Vec<(Wait, Callback<T>)>,
+	signaled: Vec<(Wait, Callback<T>)>
 }
 
 impl<T> cycles::Worker<T> for Worker<T> {
 	fn cycle(&mut self, _: &T) {
-		self.signaled = self.fences.drain_filter(|(fence, _)| fence.is_signaled().expect("fence error")).map(|(_, f)| f).collect();
+		self.signaled.extend(self.waiting.drain_filter(|(wait, _)| wait.is_signaled()));
 	}
 
 	fn apply(&mut self, state: &mut T) {
-		while let Some(new_fence) = self.pending_fences.pop() {
-			self.fences.push(new_fence)
+		while let Some(new_wait) = self.pending.pop() {
+			self.waiting.push(new_wait)
 		}
 
-		for f in self.signaled.drain(..) {
-			f(state)
+		for (wait, f) in self.signaled.drain(..) {
+			f(state);
+			std::mem::drop(wait)
 		}
 	}
-}
\ No newline at end of file
+}
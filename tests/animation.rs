@@ -0,0 +1,123 @@
+//! Keyframe interpolation, playback and blending.
+use std::sync::Arc;
+use glam::{
+	Vec3,
+	Quat
+};
+use engine::animation::{
+	Interpolation,
+	Keyframe,
+	Track,
+	Clip,
+	Playback,
+	Transform,
+	Animator
+};
+
+const EPSILON: f32 = 1e-5;
+
+fn assert_close(a: Vec3, b: Vec3) {
+	assert!((a - b).abs().max_element() < EPSILON, "{:?} != {:?}", a, b)
+}
+
+fn track(interpolation: Interpolation) -> Track<Vec3> {
+	Track::new(interpolation, vec![
+		Keyframe::new(0.0, Vec3::zero()),
+		Keyframe::new(1.0, Vec3::new(2.0, 0.0, 0.0)),
+		Keyframe::new(2.0, Vec3::new(2.0, 4.0, 0.0))
+	])
+}
+
+#[test]
+fn step() {
+	let track = track(Interpolation::Step);
+	assert_close(track.sample(0.5), Vec3::zero());
+	assert_close(track.sample(1.5), Vec3::new(2.0, 0.0, 0.0))
+}
+
+#[test]
+fn linear() {
+	let track = track(Interpolation::Linear);
+	assert_close(track.sample(0.5), Vec3::new(1.0, 0.0, 0.0));
+	assert_close(track.sample(1.25), Vec3::new(2.0, 1.0, 0.0))
+}
+
+#[test]
+fn cubic_passes_through_keyframes() {
+	let track = track(Interpolation::Cubic);
+	assert_close(track.sample(0.0), Vec3::zero());
+	assert_close(track.sample(1.0), Vec3::new(2.0, 0.0, 0.0));
+	assert_close(track.sample(2.0), Vec3::new(2.0, 4.0, 0.0))
+}
+
+#[test]
+fn cubic_catmull_rom() {
+	let track = track(Interpolation::Cubic);
+	// First and last segments reuse their end keyframe as missing neighbour.
+	assert_close(track.sample(0.5), Vec3::new(1.0, -0.25, 0.0));
+	assert_close(track.sample(1.5), Vec3::new(2.125, 2.0, 0.0))
+}
+
+#[test]
+fn clamped() {
+	let track = track(Interpolation::Linear);
+	assert_close(track.sample(-1.0), Vec3::zero());
+	assert_close(track.sample(3.0), Vec3::new(2.0, 4.0, 0.0))
+}
+
+#[test]
+fn rotation_slerp() {
+	let track = Track::new(Interpolation::Linear, vec![
+		Keyframe::new(0.0, Quat::identity()),
+		Keyframe::new(1.0, Quat::from_rotation_z(std::f32::consts::FRAC_PI_2))
+	]);
+
+	let rotated = track.sample(0.5) * Vec3::unit_x();
+	let expected = Quat::from_rotation_z(std::f32::consts::FRAC_PI_4) * Vec3::unit_x();
+	assert_close(rotated, expected)
+}
+
+#[test]
+fn looping_playback() {
+	let clip = Arc::new(Clip::new().with_translation(track(Interpolation::Linear)));
+
+	let mut playback = Playback::new(&clip).looping().with_speed(2.0);
+	playback.advance(1.25);
+	assert!((playback.time() - 0.5).abs() < EPSILON);
+	assert!(!playback.is_finished());
+
+	let mut once = Playback::new(&clip);
+	once.advance(3.0);
+	assert!(once.is_finished());
+	assert_close(once.sample().translation, Vec3::new(2.0, 4.0, 0.0))
+}
+
+#[test]
+fn blending() {
+	let a = Transform::new(Vec3::zero(), Quat::identity(), Vec3::one());
+	let b = Transform::new(Vec3::new(4.0, 0.0, 0.0), Quat::identity(), Vec3::splat(3.0));
+
+	let blended = Transform::blend(vec![(a, 3.0), (b, 1.0)]).unwrap();
+	assert_close(blended.translation, Vec3::new(1.0, 0.0, 0.0));
+	assert_close(blended.scale, Vec3::splat(1.5));
+
+	assert!(Transform::blend(vec![(a, 0.0)]).is_none())
+}
+
+#[test]
+fn finished_playbacks_retired() {
+	let clip = Arc::new(Clip::new().with_translation(track(Interpolation::Linear)));
+	let mut animator = Animator::new(|_: &mut (), _: &u32, _: &Transform| ());
+
+	animator.play(0, Playback::new(&clip));
+	animator.play(1, Playback::new(&clip).looping());
+
+	animator.update(3.0);
+	assert_eq!(animator.transforms().len(), 2);
+	assert!(animator.playbacks(&0).is_empty());
+	assert_eq!(animator.playbacks(&1).len(), 1);
+
+	animator.update(0.5);
+	assert_eq!(animator.transforms().len(), 1);
+	assert_eq!(animator.transforms()[0].0, 1)
+}
@@ -0,0 +1,145 @@
+//! Bounding volumes and intersection tests.
+use glam::{
+	Vec3,
+	Mat4,
+	Quat
+};
+use engine::{
+	bounds::{
+		Aabb,
+		Obb,
+		Frustum,
+		Sphere,
+		Ray
+	},
+	camera::{
+		Projection,
+		Perspective
+	}
+};
+
+fn frustum(perspective: &Perspective) -> Frustum {
+	let view = Mat4::look_at_rh(Vec3::zero(), -Vec3::unit_z(), Vec3::unit_y());
+	Frustum::from_matrix(&(*perspective.matrix() * view))
+}
+
+fn assert_frustum(frustum: &Frustum, bounded: bool) {
+	assert!(frustum.planes.iter().all(|plane| plane.map(|p| p.normal.length().is_finite() && p.distance.is_finite()).unwrap_or(true)));
+
+	assert!(frustum.contains(Vec3::new(0.0, 0.0, -5.0)));
+	assert!(!frustum.contains(Vec3::new(0.0, 0.0, 5.0)), "behind the camera");
+	assert!(!frustum.contains(Vec3::new(0.0, 0.0, -0.05)), "before the near plane");
+	assert!(!frustum.contains(Vec3::new(100.0, 0.0, -5.0)), "outside the side planes");
+	assert_eq!(frustum.contains(Vec3::new(0.0, 0.0, -1e5)), !bounded, "beyond the far plane");
+
+	assert!(frustum.intersects_sphere(&Sphere::new(Vec3::new(0.0, 0.0, -5.0), 1.0)));
+	assert!(!frustum.intersects_sphere(&Sphere::new(Vec3::new(0.0, 0.0, 5.0), 1.0)))
+}
+
+#[test]
+fn frustum_finite() {
+	assert_frustum(&frustum(&Perspective::fovy(std::f32::consts::FRAC_PI_2, 1.0, 0.1, 100.0)), true)
+}
+
+#[test]
+fn frustum_reversed_z() {
+	assert_frustum(&frustum(&Perspective::fovy(std::f32::consts::FRAC_PI_2, 1.0, 0.1, 100.0).with_reversed_z(true)), true)
+}
+
+#[test]
+fn frustum_infinite_far() {
+	let frustum = frustum(&Perspective::fovy(std::f32::consts::FRAC_PI_2, 1.0, 0.1, 100.0).with_infinite_far());
+	assert_eq!(frustum.planes.iter().filter(|plane| plane.is_none()).count(), 1);
+	assert_frustum(&frustum, false)
+}
+
+#[test]
+fn frustum_infinite_far_reversed_z() {
+	let frustum = frustum(&Perspective::fovy(std::f32::consts::FRAC_PI_2, 1.0, 0.1, 100.0).with_infinite_far().with_reversed_z(true));
+	assert_eq!(frustum.planes.iter().filter(|plane| plane.is_none()).count(), 1);
+	assert_frustum(&frustum, false)
+}
+
+#[test]
+fn ray_aabb() {
+	let aabb = Aabb::new(Vec3::splat(-1.0), Vec3::splat(1.0));
+	assert_eq!(Ray::new(Vec3::new(-5.0, 0.5, 0.5), Vec3::unit_x()).intersect_aabb(&aabb), Some(4.0));
+	assert_eq!(Ray::new(Vec3::zero(), Vec3::unit_x()).intersect_aabb(&aabb), Some(0.0), "origin inside");
+	assert_eq!(Ray::new(Vec3::new(5.0, 0.0, 0.0), Vec3::unit_x()).intersect_aabb(&aabb), None, "pointing away");
+	assert_eq!(Ray::new(Vec3::new(-5.0, 5.0, 0.0), Vec3::unit_x()).intersect_aabb(&aabb), None, "passing by")
+}
+
+#[test]
+fn ray_aabb_parallel() {
+	let aabb = Aabb::new(Vec3::splat(-1.0), Vec3::splat(1.0));
+
+	// Origin on the boundary of the slabs the ray is parallel to.
+	assert_eq!(Ray::new(Vec3::new(-5.0, 1.0, -1.0), Vec3::unit_x()).intersect_aabb(&aabb), Some(4.0));
+	assert_eq!(Ray::new(Vec3::new(-1.0, -1.0, -5.0), Vec3::unit_z()).intersect_aabb(&aabb), Some(4.0));
+	assert_eq!(Ray::new(Vec3::new(-5.0, 1.5, 0.0), Vec3::unit_x()).intersect_aabb(&aabb), None)
+}
+
+#[test]
+fn obb_from_aabb() {
+	let aabb = Aabb::new(Vec3::splat(-1.0), Vec3::splat(1.0));
+	let rotation = Quat::from_rotation_y(std::f32::consts::FRAC_PI_4);
+	let obb = Obb::from_aabb(&aabb, &Mat4::from_scale_rotation_translation(Vec3::new(2.0, 1.0, 1.0), rotation, Vec3::unit_x()));
+	assert!((obb.half_extents - Vec3::new(2.0, 1.0, 1.0)).length() < 1e-5);
+	assert!((obb.center - Vec3::unit_x()).length() < 1e-5);
+	assert!(obb.contains(Vec3::unit_x() + rotation * Vec3::new(1.9, 0.0, 0.0)));
+	assert!(!obb.contains(Vec3::unit_x() + rotation * Vec3::new(0.0, 0.0, 1.1)))
+}
+
+#[test]
+fn obb_from_aabb_zero_scale() {
+	let aabb = Aabb::new(Vec3::splat(-1.0), Vec3::splat(1.0));
+
+	for scale in [Vec3::new(0.0, 1.0, 1.0), Vec3::new(1.0, 0.0, 0.0), Vec3::zero()].iter() {
+		let obb = Obb::from_aabb(&aabb, &Mat4::from_scale(*scale));
+		assert_eq!(obb.half_extents, *scale);
+		for i in 0..3 {
+			assert!((obb.axes[i].length() - 1.0).abs() < 1e-5, "axes must be unit vectors");
+			assert!(obb.axes[i].dot(obb.axes[(i + 1) % 3]).abs() < 1e-5, "axes must be orthogonal")
+		}
+		assert!(obb.contains(Vec3::zero()))
+	}
+}
+
+#[test]
+fn ray_obb() {
+	let aabb = Aabb::new(Vec3::splat(-1.0), Vec3::splat(1.0));
+	let rotation = Quat::from_rotation_z(std::f32::consts::FRAC_PI_4);
+	let obb = Obb::from_aabb(&aabb, &Mat4::from_rotation_translation(rotation, Vec3::zero()));
+
+	// The rotated box reaches sqrt(2) along the x axis.
+	let d = Ray::new(Vec3::new(-5.0, 0.0, 0.0), Vec3::unit_x()).intersect_obb(&obb).unwrap();
+	assert!((d - (5.0 - std::f32::consts::SQRT_2)).abs() < 1e-5);
+
+	assert_eq!(Ray::new(Vec3::new(-5.0, 1.5, 0.0), Vec3::unit_x()).intersect_obb(&obb), None);
+	assert_eq!(Ray::new(Vec3::zero(), Vec3::unit_y()).intersect_obb(&obb), Some(0.0))
+}
+
+#[test]
+fn ray_sphere() {
+	let sphere = Sphere::new(Vec3::new(0.0, 0.0, -5.0), 1.0);
+	assert_eq!(Ray::new(Vec3::zero(), -Vec3::unit_z()).intersect_sphere(&sphere), Some(4.0));
+	assert_eq!(Ray::new(Vec3::new(0.0, 0.0, -5.0), Vec3::unit_x()).intersect_sphere(&sphere), Some(0.0), "origin inside");
+	assert_eq!(Ray::new(Vec3::zero(), Vec3::unit_z()).intersect_sphere(&sphere), None, "pointing away");
+	assert_eq!(Ray::new(Vec3::new(0.0, 1.5, 0.0), -Vec3::unit_z()).intersect_sphere(&sphere), None, "passing by")
+}
+
+#[test]
+fn ray_triangle() {
+	let (a, b, c) = (Vec3::new(0.0, 0.0, -2.0), Vec3::new(1.0, 0.0, -2.0), Vec3::new(0.0, 1.0, -2.0));
+
+	let (d, u, v) = Ray::new(Vec3::new(0.25, 0.5, 0.0), -Vec3::unit_z()).intersect_triangle(a, b, c).unwrap();
+	assert!((d - 2.0).abs() < 1e-5);
+	assert!((u - 0.25).abs() < 1e-5 && (v - 0.5).abs() < 1e-5);
+
+	// Both faces are hit.
+	assert!(Ray::new(Vec3::new(0.25, 0.25, -4.0), Vec3::unit_z()).intersect_triangle(a, b, c).is_some());
+
+	assert_eq!(Ray::new(Vec3::new(0.75, 0.75, 0.0), -Vec3::unit_z()).intersect_triangle(a, b, c), None, "outside");
+	assert_eq!(Ray::new(Vec3::new(0.25, 0.25, 0.0), Vec3::unit_z()).intersect_triangle(a, b, c), None, "behind");
+	assert_eq!(Ray::new(Vec3::new(0.0, 0.0, -2.0), Vec3::unit_x()).intersect_triangle(a, b, c), None, "parallel")
+}
@@ -0,0 +1,170 @@
+//! Camera controls.
+use std::f32::consts::{
+	PI,
+	FRAC_PI_2
+};
+use glam::{
+	Vec2,
+	Vec3
+};
+use engine::{
+	camera::{
+		Satellite,
+		Controller,
+		Fly,
+		FirstPerson
+	},
+	input::{
+		InputState,
+		Event,
+		Key
+	}
+};
+
+const EPSILON: f32 = 1e-5;
+
+fn satellite() -> Satellite {
+	Satellite::new(Vec3::zero(), 10.0, FRAC_PI_2, 0.0)
+}
+
+#[test]
+fn satellite_drag() {
+	let mut camera = satellite();
+	camera.set_sensitivity(0.01);
+
+	// Dragging to the right turns the camera to the left, dragging down moves it up.
+	camera.drag(Vec2::new(100.0, 50.0));
+	assert!((camera.azimuthal() + 1.0).abs() < EPSILON);
+	assert!((camera.polar() - (FRAC_PI_2 - 0.5)).abs() < EPSILON);
+
+	let (delta_polar, delta_azimuthal) = camera.drag_to_angles(camera.angles_to_drag(0.2, -0.3));
+	assert!((delta_polar - 0.2).abs() < EPSILON && (delta_azimuthal + 0.3).abs() < EPSILON)
+}
+
+#[test]
+fn satellite_drag_clamping() {
+	let mut camera = satellite();
+
+	// Never reaches the poles.
+	camera.drag(Vec2::new(0.0, 1e6));
+	assert!(camera.polar() > 0.0 && camera.polar() < FRAC_PI_2);
+	camera.drag(Vec2::new(0.0, -1e6));
+	assert!(camera.polar() > FRAC_PI_2 && camera.polar() < PI);
+
+	camera.set_polar_range(0.5, 1.0);
+	assert!((camera.polar() - 1.0).abs() < EPSILON, "current angle is clamped");
+	camera.drag(Vec2::new(0.0, 1e6));
+	assert!((camera.polar() - 0.5).abs() < EPSILON)
+}
+
+#[test]
+fn satellite_zoom_clamping() {
+	let mut camera = satellite();
+	camera.zoom(0.5);
+	assert!((camera.distance() - 5.0).abs() < EPSILON);
+
+	camera.set_distance_range(1.0, 4.0);
+	assert!((camera.distance() - 4.0).abs() < EPSILON, "current distance is clamped");
+
+	camera.zoom(0.1);
+	assert!((camera.distance() - 1.0).abs() < EPSILON);
+	camera.zoom(100.0);
+	assert!((camera.distance() - 4.0).abs() < EPSILON)
+}
+
+#[test]
+fn satellite_damping() {
+	let mut camera = satellite();
+	camera.set_damping(1.0);
+	camera.set_distance_range(2.0, 20.0);
+	camera.zoom(2.0);
+	assert!((camera.distance() - 10.0).abs() < EPSILON, "damped zoom is not applied immediately");
+
+	camera.update(1.0);
+	let expected = 10.0 + 10.0 * (1.0 - (-1.0f32).exp());
+	assert!((camera.distance() - expected).abs() < 1e-4);
+
+	camera.snap();
+	assert!((camera.distance() - 20.0).abs() < EPSILON)
+}
+
+fn input(events: &[Event]) -> InputState {
+	let mut input = InputState::new();
+	for event in events {
+		input.handle(*event)
+	}
+	input
+}
+
+fn assert_close(a: Vec3, b: Vec3) {
+	assert!((a - b).abs().max_element() < 1e-4, "{:?} != {:?}", a, b)
+}
+
+#[test]
+fn fly_movement() {
+	// Looking toward `+x`.
+	let mut fly = Fly::new(Vec3::zero(), 0.0, 0.0);
+	fly.settings_mut().acceleration = std::f32::INFINITY;
+
+	let forward = input(&[Event::KeyPressed(Key::W)]);
+	fly.update(&forward, 0.5);
+	assert_close(fly.position(), Vec3::new(2.5, 0.0, 0.0));
+	fly.update(&forward, 0.1);
+	assert_close(fly.position(), Vec3::new(3.0, 0.0, 0.0));
+
+	// Boost, and no movement without elapsed time.
+	let boosted = input(&[Event::KeyPressed(Key::W), Event::KeyPressed(Key::Shift)]);
+	fly.update(&boosted, 0.0);
+	assert_close(fly.position(), Vec3::new(3.0, 0.0, 0.0));
+	fly.update(&boosted, 0.1);
+	assert_close(fly.position(), Vec3::new(5.0, 0.0, 0.0));
+
+	fly.update(&input(&[Event::KeyPressed(Key::E)]), 1.0);
+	assert_close(fly.position(), Vec3::new(5.0, 0.0, 5.0))
+}
+
+#[test]
+fn fly_acceleration() {
+	let mut fly = Fly::new(Vec3::zero(), 0.0, 0.0);
+	let forward = input(&[Event::KeyPressed(Key::W)]);
+
+	// Default acceleration of 20 units/s², up to 5 units/s.
+	fly.update(&forward, 0.1);
+	assert_close(fly.velocity(), Vec3::new(2.0, 0.0, 0.0));
+	fly.update(&forward, 1.0);
+	assert_close(fly.velocity(), Vec3::new(5.0, 0.0, 0.0));
+
+	// Decelerates once released.
+	fly.update(&InputState::new(), 0.1);
+	assert_close(fly.velocity(), Vec3::new(3.0, 0.0, 0.0))
+}
+
+#[test]
+fn first_person_movement() {
+	// Looking toward `+y` and up: the camera still walks on the horizontal plane.
+	let mut camera = FirstPerson::new(Vec3::new(0.0, 0.0, 1.8), std::f32::consts::FRAC_PI_2, 0.5);
+	camera.settings_mut().acceleration = std::f32::INFINITY;
+
+	camera.update(&input(&[Event::KeyPressed(Key::W), Event::KeyPressed(Key::E)]), 1.0);
+	assert_close(camera.position(), Vec3::new(0.0, 5.0, 1.8))
+}
+
+#[test]
+fn look_and_scroll() {
+	let mut fly = Fly::new(Vec3::zero(), 0.0, 0.0);
+	fly.settings_mut().acceleration = std::f32::INFINITY;
+	fly.settings_mut().sensitivity = 0.01;
+
+	// Scrolling one line up multiplies the speed.
+	fly.update(&input(&[Event::Scrolled(Vec2::new(0.0, 1.0))]), 0.0);
+	assert!((fly.settings().speed - 6.0).abs() < EPSILON);
+
+	// Dragging a single touch point to the left turns the camera to the left, from `+x` to `+y`.
+	let mut touch = input(&[Event::TouchStarted(0, Vec2::zero())]);
+	touch.handle(Event::TouchMoved(0, Vec2::new(-50.0 * std::f32::consts::PI, 0.0)));
+	fly.update(&touch, 0.0);
+	touch.end_frame();
+	touch.handle(Event::KeyPressed(Key::W));
+	fly.update(&touch, 1.0);
+	assert_close(fly.position(), Vec3::new(0.0, 6.0, 0.0))
+}
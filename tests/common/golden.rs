@@ -0,0 +1,197 @@
+//! Golden image comparison.
+//!
+//! Rendered images are compared against reference PNG files stored in `tests/golden`.
+//! When a comparison fails, the rendered image and a diff image are written next to the reference.
+//! Setting the `UPDATE_GOLDEN` environment variable overwrites the references instead.
+use std::{
+	fs::File,
+	io::BufWriter,
+	path::{
+		Path,
+		PathBuf
+	}
+};
+
+/// RGBA8 image.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Image {
+	pub width: u32,
+	pub height: u32,
+
+	/// Tightly packed RGBA8 pixels, row by row starting from the top.
+	pub pixels: Vec<u8>
+}
+
+impl Image {
+	pub fn new(width: u32, height: u32, pixels: Vec<u8>) -> Self {
+		assert_eq!(pixels.len(), (width * height * 4) as usize);
+		Self {
+			width,
+			height,
+			pixels
+		}
+	}
+
+	/// Image filled with the given color.
+	pub fn filled(width: u32, height: u32, color: [u8; 4]) -> Self {
+		Self::new(width, height, color.iter().copied().cycle().take((width * height * 4) as usize).collect())
+	}
+
+	pub fn pixel(&self, x: u32, y: u32) -> [u8; 4] {
+		let i = ((y * self.width + x) * 4) as usize;
+		[self.pixels[i], self.pixels[i + 1], self.pixels[i + 2], self.pixels[i + 3]]
+	}
+
+	pub fn set_pixel(&mut self, x: u32, y: u32, color: [u8; 4]) {
+		let i = ((y * self.width + x) * 4) as usize;
+		self.pixels[i..(i + 4)].copy_from_slice(&color)
+	}
+
+	pub fn load<P: AsRef<Path>>(path: P) -> std::io::Result<Self> {
+		let decoder = png::Decoder::new(File::open(path)?);
+		let (info, mut reader) = decoder.read_info().map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+
+		if info.color_type != png::ColorType::RGBA || info.bit_depth != png::BitDepth::Eight {
+			return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "golden images must be RGBA8"))
+		}
+
+		let mut pixels = vec![0; info.buffer_size()];
+		reader.next_frame(&mut pixels).map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+		Ok(Self::new(info.width, info.height, pixels))
+	}
+
+	pub fn save<P: AsRef<Path>>(&self, path: P) -> std::io::Result<()> {
+		let mut encoder = png::Encoder::new(BufWriter::new(File::create(path)?), self.width, self.height);
+		encoder.set_color(png::ColorType::RGBA);
+		encoder.set_depth(png::BitDepth::Eight);
+		let mut writer = encoder.write_header().map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
+		writer.write_image_data(&self.pixels).map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))
+	}
+}
+
+/// Comparison tolerance.
+#[derive(Clone, Copy, Debug)]
+pub struct Tolerance {
+	/// Maximum difference between two channels for pixels to be considered equal.
+	pub channel: u8,
+
+	/// Maximum ratio of different pixels.
+	///
+	/// Absorbs rasterization differences between drivers on edges.
+	pub pixels: f32
+}
+
+impl Tolerance {
+	pub const EXACT: Tolerance = Tolerance {
+		channel: 0,
+		pixels: 0.0
+	};
+}
+
+impl Default for Tolerance {
+	fn default() -> Self {
+		Self {
+			channel: 2,
+			pixels: 0.001
+		}
+	}
+}
+
+/// Comparison result.
+pub struct Comparison {
+	/// Number of different pixels.
+	pub different: usize,
+
+	/// Greatest channel difference.
+	pub max_difference: u8,
+
+	/// Diff image.
+	///
+	/// Equal pixels are dimmed and different pixels are drawn in red.
+	pub diff: Image
+}
+
+impl Comparison {
+	pub fn is_within(&self, tolerance: Tolerance) -> bool {
+		let len = (self.diff.width * self.diff.height) as f32;
+		self.different as f32 <= tolerance.pixels * len
+	}
+}
+
+/// Compare two images of the same size.
+pub fn compare(image: &Image, reference: &Image, tolerance: Tolerance) -> Comparison {
+	assert_eq!((image.width, image.height), (reference.width, reference.height), "image sizes differ");
+
+	let mut different = 0;
+	let mut max_difference = 0;
+	let mut diff = Image::filled(image.width, image.height, [0, 0, 0, 255]);
+
+	for y in 0..image.height {
+		for x in 0..image.width {
+			let a = image.pixel(x, y);
+			let b = reference.pixel(x, y);
+			let d = a.iter().zip(&b).map(|(a, b)| (*a as i16 - *b as i16).abs() as u8).max().unwrap();
+			max_difference = std::cmp::max(max_difference, d);
+
+			if d > tolerance.channel {
+				different += 1;
+				diff.set_pixel(x, y, [255, 0, 0, 255])
+			} else {
+				diff.set_pixel(x, y, [b[0] / 4, b[1] / 4, b[2] / 4, 255])
+			}
+		}
+	}
+
+	Comparison {
+		different,
+		max_difference,
+		diff
+	}
+}
+
+fn golden_path(name: &str, suffix: &str) -> PathBuf {
+	Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("golden").join(format!("{}{}.png", name, suffix))
+}
+
+/// Check the given image against the golden image of the given name.
+///
+/// Panics if the images differ beyond the tolerance, after writing
+/// `<name>.actual.png` and `<name>.diff.png`.
+/// Also panics if the golden image does not exist, after writing `<name>.actual.png`.
+/// If `UPDATE_GOLDEN` is set, the golden image is written instead.
+pub fn assert_golden(name: &str, image: &Image, tolerance: Tolerance) {
+	let path = golden_path(name, "");
+
+	if std::env::var_os("UPDATE_GOLDEN").is_some() {
+		image.save(&path).expect("unable to write golden image");
+		return
+	}
+
+	if !path.exists() {
+		let actual_path = golden_path(name, ".actual");
+		image.save(&actual_path).expect("unable to write rendered image");
+		panic!(
+			"`{}` has no golden image, see {} (set `UPDATE_GOLDEN` to accept it)",
+			name,
+			actual_path.display()
+		)
+	}
+
+	let reference = Image::load(&path).expect("unable to read golden image");
+	let comparison = compare(image, &reference, tolerance);
+
+	if !comparison.is_within(tolerance) {
+		let actual_path = golden_path(name, ".actual");
+		let diff_path = golden_path(name, ".diff");
+		image.save(&actual_path).expect("unable to write rendered image");
+		comparison.diff.save(&diff_path).expect("unable to write diff image");
+
+		panic!(
+			"`{}` differs from its golden image: {} different pixels (max channel difference {}), see {}",
+			name,
+			comparison.different,
+			comparison.max_difference,
+			diff_path.display()
+		)
+	}
+}
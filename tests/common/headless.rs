@@ -0,0 +1,168 @@
+//! Headless rendering.
+//!
+//! Renders a scene through `render::Worker` into an `OffscreenTarget`,
+//! using the first available Vulkan device (a software driver such as lavapipe on CI).
+use std::{
+	sync::Arc,
+	path::Path,
+	process::Command
+};
+use glam::Mat4;
+use magma::{
+	Instance,
+	Device,
+	DeviceOwned,
+	device,
+	mem::alloc::Slab,
+	pipeline::shader
+};
+use scene::{
+	Scene,
+	Id
+};
+use engine::{
+	View,
+	view::{
+		Object,
+		Geometry,
+		Material,
+		material::FragmentShader,
+		geometry::projection::{
+			Projection,
+			Standard,
+			VertexShader,
+			CameraProjection
+		}
+	},
+	render::{
+		self,
+		OffscreenTarget,
+		FrustumPointOfView,
+		Frames,
+		frames::DEFAULT_FRAMES_IN_FLIGHT
+	},
+	sync::Loader
+};
+use super::golden::Image;
+
+/// Headless device.
+pub struct Headless {
+	queue: device::Queue
+}
+
+impl Headless {
+	/// Create a headless device, if a Vulkan driver is available.
+	pub fn new() -> Option<Self> {
+		let instance = Instance::new(&[]).ok()?;
+		let physical_device = instance.physical_devices().into_iter().next()?;
+		let family = physical_device.queue_families().find(|family| family.supports_graphics())?;
+		let (_, mut queues) = Device::new(&physical_device, &[(family, &[1.0])]).ok()?;
+
+		Some(Self {
+			queue: queues.next()?
+		})
+	}
+
+	pub fn device(&self) -> &Arc<Device> {
+		self.queue.device()
+	}
+
+	/// Compile the given GLSL shader of the engine sources with `glslc`.
+	pub fn shader(&self, source: &str) -> Arc<shader::Module> {
+		let source = Path::new(env!("CARGO_MANIFEST_DIR")).join("src").join(source);
+		let output = Path::new(env!("CARGO_TARGET_TMPDIR")).join(source.file_name().unwrap()).with_extension("spv");
+
+		let status = Command::new("glslc").arg(&source).arg("-o").arg(&output).status().expect("unable to run glslc");
+		assert!(status.success(), "unable to compile {}", source.display());
+
+		let spirv = std::fs::read(&output).expect("unable to read compiled shader");
+		Arc::new(shader::Module::new(self.device(), &spirv).expect("unable to create shader module"))
+	}
+
+	/// Standard projection, from `view/geometry/projection/shaders/standard.vert`.
+	pub fn standard_projection(&self) -> Arc<dyn Projection> {
+		Arc::new(Standard::new(VertexShader::new(self.shader("view/geometry/projection/shaders/standard.vert"))))
+	}
+
+	/// Depth material, from `view/material/shaders/depth.frag`.
+	pub fn depth_material(&self) -> Arc<dyn Material> {
+		Arc::new(TestMaterial(FragmentShader::new(self.shader("view/material/shaders/depth.frag"))))
+	}
+
+	/// Render the given scene with the given camera, and read back the result.
+	pub fn render<T, G: render::Generator<T>>(
+		&self,
+		scene: &Scene<T, ()>,
+		objects: &[Id<T>],
+		generator: G,
+		view: Mat4,
+		projection: Mat4,
+		extent: (u32, u32)
+	) -> Image where T: 'static, Id<T>: Copy + Eq + std::hash::Hash {
+		// Single queue: uploads are recorded in the rendered frames, never submitted concurrently.
+		let (loader, inline_loader) = Loader::inline(Slab::new(self.device()), self.queue.clone());
+
+		let target = OffscreenTarget::new(Slab::new(self.device()), self.queue.clone(), extent);
+		let mut point_of_view = FrustumPointOfView::new(view, projection);
+		for id in objects {
+			point_of_view.insert(*id, engine::bounds::Aabb::new(glam::Vec3::splat(-1e3), glam::Vec3::splat(1e3)))
+		}
+
+		let mut worker = render::Worker::new(target, self.queue.clone(), loader, point_of_view, generator);
+		worker.set_inline_loader(inline_loader);
+
+		// Buffers are requested by the first frame, and resolved once the frame uploading them has been executed:
+		// render until a frame is recorded with every buffer loaded.
+		let mut frames = Frames::new(self.queue.clone(), DEFAULT_FRAMES_IN_FLIGHT);
+		let mut rendered = 0;
+		loop {
+			cycles::Worker::cycle(&mut worker, scene);
+			let loaded = rendered > 0 && !worker.is_loading();
+
+			let framebuffer = worker.target().framebuffer().clone();
+			worker.render_frame(&mut frames, &framebuffer, &CameraProjection::new(view, projection));
+			rendered += 1;
+
+			if loaded {
+				break
+			}
+
+			assert!(rendered < MAX_LOADING_FRAMES, "resources still loading after {} frames", rendered)
+		}
+		frames.wait_idle();
+
+		let (width, height) = extent;
+		Image::new(width, height, worker.target_mut().read_rgba8())
+	}
+}
+
+/// Maximum number of frames rendered while waiting for the resources to be loaded.
+const MAX_LOADING_FRAMES: usize = 64;
+
+struct TestMaterial(FragmentShader);
+
+impl Material for TestMaterial {
+	fn shader(&self) -> &FragmentShader {
+		&self.0
+	}
+}
+
+/// Generator building a single object view for every scene object.
+pub struct ObjectGenerator<F> {
+	f: F
+}
+
+impl<F> ObjectGenerator<F> {
+	pub fn new(f: F) -> Self {
+		Self {
+			f
+		}
+	}
+}
+
+impl<T, F: Fn(&T) -> (Geometry, Arc<dyn Projection>, Arc<dyn Material>)> render::Generator<T> for ObjectGenerator<F> {
+	fn view(&self, object: &T) -> View {
+		let (geometry, projection, material) = (self.f)(object);
+		View::Object(Object::new(geometry, projection, material))
+	}
+}
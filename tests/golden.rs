@@ -0,0 +1,122 @@
+//! Golden image rendering tests.
+//!
+//! Rendering tests need a Vulkan driver and `glslc`.
+//! Reference images are rendered with the lavapipe software driver
+//! (select it with `VK_ICD_FILENAMES` on machines with other drivers).
+//! Without driver, rendering tests are skipped, unless `REQUIRE_VULKAN` is set,
+//! as it must be on CI, in which case they fail.
+//! Set `UPDATE_GOLDEN=1` to update the reference images.
+use glam::{
+	Vec3,
+	Mat4
+};
+use scene::Scene;
+use engine::{
+	camera::{
+		Projection,
+		Perspective
+	},
+	view::Geometry
+};
+
+mod common;
+
+use common::{
+	golden::{
+		Image,
+		Tolerance,
+		compare,
+		assert_golden
+	},
+	headless::{
+		Headless,
+		ObjectGenerator
+	}
+};
+
+#[test]
+fn compare_identical() {
+	let image = Image::filled(4, 4, [10, 20, 30, 255]);
+	let comparison = compare(&image, &image, Tolerance::EXACT);
+	assert_eq!(comparison.different, 0);
+	assert!(comparison.is_within(Tolerance::EXACT))
+}
+
+#[test]
+fn compare_within_channel_tolerance() {
+	let reference = Image::filled(4, 4, [10, 20, 30, 255]);
+	let image = Image::filled(4, 4, [12, 18, 30, 255]);
+	let comparison = compare(&image, &reference, Tolerance::default());
+	assert_eq!(comparison.max_difference, 2);
+	assert!(comparison.is_within(Tolerance::default()))
+}
+
+#[test]
+fn compare_different() {
+	let reference = Image::filled(4, 4, [10, 20, 30, 255]);
+	let mut image = reference.clone();
+	image.set_pixel(1, 2, [200, 20, 30, 255]);
+
+	let comparison = compare(&image, &reference, Tolerance::default());
+	assert_eq!(comparison.different, 1);
+	assert_eq!(comparison.diff.pixel(1, 2), [255, 0, 0, 255]);
+	assert_eq!(comparison.diff.pixel(0, 0), [2, 5, 7, 255]);
+	assert!(!comparison.is_within(Tolerance::default()));
+	assert!(comparison.is_within(Tolerance { channel: 0, pixels: 1.0 / 16.0 }))
+}
+
+struct Triangle;
+
+fn triangle_geometry() -> Geometry {
+	let vertices = [
+		Vec3::new(-1.0, -1.0, 0.0),
+		Vec3::new(1.0, -1.0, 0.0),
+		Vec3::new(0.0, 1.0, 0.0)
+	];
+
+	let bytes: Vec<u8> = vertices.iter().flat_map(|v| v.as_ref().iter().flat_map(|c| c.to_ne_bytes().to_vec())).collect();
+	Geometry::new(geometer::AbstractGeometry::new(bytes, vec![vec![0, 1, 2]]))
+}
+
+/// Headless device, or `None` if the rendering tests must be skipped.
+fn headless() -> Option<Headless> {
+	let headless = Headless::new();
+
+	if headless.is_none() {
+		if std::env::var_os("REQUIRE_VULKAN").is_some() {
+			panic!("no Vulkan driver available")
+		}
+
+		eprintln!("no Vulkan driver available, skipping")
+	}
+
+	headless
+}
+
+/// Renders a triangle through the render worker with the standard projection and depth material.
+///
+/// Covers the camera matrices push constants, back-face culling (the triangle is counter-clockwise
+/// in the framebuffer and must be kept), and the offscreen target readback.
+/// With reversed-Z, the depth material saturates near the camera:
+/// the triangle is drawn in white on the black background.
+/// Since the projection does not flip the `y` axis, the apex of the triangle is at the bottom of the image.
+#[test]
+fn render_triangle() {
+	let headless = match headless() {
+		Some(headless) => headless,
+		None => return
+	};
+
+	let mut scene = Scene::new();
+	let triangle = scene.insert(Triangle);
+
+	let projection = headless.standard_projection();
+	let material = headless.depth_material();
+	let generator = ObjectGenerator::new(move |_: &Triangle| (triangle_geometry(), projection.clone(), material.clone()));
+
+	let view = Mat4::look_at_rh(Vec3::new(0.0, 0.0, 3.0), Vec3::zero(), Vec3::unit_y());
+	let perspective = Perspective::fovy(std::f32::consts::FRAC_PI_3, 1.0, 0.1, 10.0).with_reversed_z(true);
+
+	let image = headless.render(&scene, &[triangle], generator, view, *perspective.matrix(), (64, 64));
+	assert_golden("triangle", &image, Tolerance::default())
+}
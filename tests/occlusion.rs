@@ -0,0 +1,122 @@
+//! Depth pyramid and point of view of the occlusion culling stage.
+#![feature(generic_associated_types)]
+use glam::{
+	Vec2,
+	Vec3,
+	Mat4
+};
+use scene::{
+	Scene,
+	Id
+};
+use engine::{
+	bounds::{
+		Aabb,
+		Volume
+	},
+	render::{
+		PointOfView,
+		OcclusionPointOfView,
+		occlusion::DepthPyramid
+	}
+};
+
+/// 4x4 depth buffer, whose top-left quarter is nearer than the rest.
+const DEPTHS: [f32; 16] = [
+	0.2, 0.3, 0.9, 0.8,
+	0.1, 0.4, 0.7, 0.6,
+	0.5, 0.5, 0.5, 0.5,
+	0.5, 0.5, 0.5, 0.5
+];
+
+#[test]
+fn depth_pyramid_levels() {
+	let pyramid = DepthPyramid::new(&DEPTHS, 4, 4, false);
+	assert_eq!(pyramid.width(), 4);
+	assert_eq!(pyramid.height(), 4);
+	assert_eq!(pyramid.level_count(), 3);
+
+	let pyramid = DepthPyramid::new(&[0.5; 15], 5, 3, false);
+	assert_eq!(pyramid.level_count(), 4);
+}
+
+#[test]
+fn depth_pyramid_farthest() {
+	let pyramid = DepthPyramid::new(&DEPTHS, 4, 4, false);
+	assert_eq!(pyramid.farthest_depth(Vec2::zero(), Vec2::one()), 0.9);
+	assert_eq!(pyramid.farthest_depth(Vec2::zero(), Vec2::splat(0.4)), 0.4);
+	assert_eq!(pyramid.farthest_depth(Vec2::new(0.0, 0.6), Vec2::new(0.4, 1.0)), 0.5);
+	assert!(pyramid.is_nearer(0.4, 0.5))
+}
+
+#[test]
+fn depth_pyramid_farthest_reversed_z() {
+	let pyramid = DepthPyramid::new(&DEPTHS, 4, 4, true);
+	assert_eq!(pyramid.farthest_depth(Vec2::zero(), Vec2::one()), 0.1);
+	assert_eq!(pyramid.farthest_depth(Vec2::new(0.6, 0.0), Vec2::new(1.0, 0.4)), 0.6);
+	assert_eq!(pyramid.farthest_depth(Vec2::new(0.0, 0.6), Vec2::new(0.4, 1.0)), 0.5);
+	assert!(pyramid.is_nearer(0.5, 0.4))
+}
+
+#[test]
+fn depth_pyramid_is_conservative() {
+	let pyramid = DepthPyramid::new(&DEPTHS, 4, 4, false);
+
+	// Any rectangle gives a depth at least as far as every texel it covers.
+	for (min, max) in [
+		(Vec2::new(0.1, 0.1), Vec2::new(0.3, 0.3)),
+		(Vec2::new(0.3, 0.1), Vec2::new(0.6, 0.4)),
+		(Vec2::new(-1.0, -1.0), Vec2::new(0.1, 0.1))
+	].iter() {
+		let depth = pyramid.farthest_depth(*min, *max);
+		let x0 = (min.x.max(0.0) * 4.0) as usize;
+		let y0 = (min.y.max(0.0) * 4.0) as usize;
+		let x1 = ((max.x * 4.0) as usize).min(3);
+		let y1 = ((max.y * 4.0) as usize).min(3);
+		for y in y0..=y1 {
+			for x in x0..=x1 {
+				assert!(DEPTHS[y * 4 + x] <= depth)
+			}
+		}
+	}
+}
+
+struct Object;
+
+/// Point of view seeing every given object, with the same bounding volume.
+struct All {
+	objects: Vec<Id<Object>>,
+	volume: Volume
+}
+
+impl PointOfView<Object, ()> for All {
+	type Iter<'a> where Object: 'a = std::slice::Iter<'a, Id<Object>>;
+
+	fn cycle(&mut self, _scene: &Scene<Object, ()>) {}
+
+	fn visible_objects<'a>(&'a self) -> Self::Iter<'a> {
+		self.objects.iter()
+	}
+
+	fn volume(&self, _object: &Id<Object>) -> Option<Volume> {
+		Some(self.volume)
+	}
+}
+
+#[test]
+fn occluded_objects_not_visible() {
+	let mut scene: Scene<Object, ()> = Scene::new();
+	let object = scene.insert(Object);
+
+	// Box behind a depth of `0.1` everywhere, seen through the identity view-projection.
+	let volume = Aabb::new(Vec3::new(-0.5, -0.5, 0.5), Vec3::new(0.5, 0.5, 0.6)).into();
+	let mut pov = OcclusionPointOfView::new(All { objects: vec![object], volume }, false);
+
+	// Unknown in the previous frame: never culled.
+	pov.cycle(&scene);
+	assert_eq!(PointOfView::<Object, ()>::visible_objects(&pov).count(), 1);
+
+	PointOfView::<Object, ()>::occlusion_culling_mut(&mut pov).unwrap().set_depth(&[0.1; 16], 4, 4, Mat4::identity());
+	pov.cycle(&scene);
+	assert_eq!(PointOfView::<Object, ()>::visible_objects(&pov).count(), 0)
+}
@@ -0,0 +1,92 @@
+//! Projection matrices, checked against the values computed by `test.rb`.
+use glam::{
+	Vec3,
+	Vec4
+};
+use engine::camera::{
+	Projection,
+	Perspective,
+	Orthographic
+};
+
+const EPSILON: f32 = 1e-5;
+
+fn project<P: Projection>(projection: &P, point: Vec3) -> Vec3 {
+	let clip = *projection.matrix() * Vec4::new(point.x, point.y, point.z, 1.0);
+	clip.truncate() / clip.w
+}
+
+fn assert_close(a: Vec3, b: Vec3) {
+	assert!((a - b).abs().max_element() < EPSILON, "{:?} != {:?}", a, b)
+}
+
+#[test]
+fn perspective_frustum() {
+	// `perspective(-2.0, 2.0, -2.0, 2.0, 2.0, 6.0)` in `test.rb`.
+	let p = Perspective::new(-2.0, 2.0, -2.0, 2.0, 2.0, 6.0);
+
+	// `test.rb` gives `[1/3, 1/3, 0]` with an OpenGL depth range of `[-1, 1]`.
+	assert_close(project(&p, Vec3::new(1.0, 1.0, -3.0)), Vec3::new(1.0 / 3.0, 1.0 / 3.0, 0.5))
+}
+
+#[test]
+fn perspective_fovx() {
+	// `fovx_perspective(1.5707963267948966, 1.0, 2.0, 6.0)` in `test.rb`.
+	let p = Perspective::fovx(std::f32::consts::FRAC_PI_2, 1.0, 2.0, 6.0);
+	assert_close(project(&p, Vec3::new(1.0, 1.0, -3.0)), Vec3::new(1.0 / 3.0, 1.0 / 3.0, 0.5))
+}
+
+#[test]
+fn perspective_fovy() {
+	let p = Perspective::fovy(std::f32::consts::FRAC_PI_2, 1.0, 2.0, 6.0);
+	assert_close(project(&p, Vec3::new(1.0, 1.0, -3.0)), Vec3::new(1.0 / 3.0, 1.0 / 3.0, 0.5))
+}
+
+#[test]
+fn perspective_depth_range() {
+	let p = Perspective::new(-2.0, 2.0, -2.0, 2.0, 2.0, 6.0);
+	assert!(project(&p, Vec3::new(0.0, 0.0, -2.0)).z.abs() < EPSILON);
+	assert!((project(&p, Vec3::new(0.0, 0.0, -6.0)).z - 1.0).abs() < EPSILON);
+}
+
+#[test]
+fn perspective_reversed_z() {
+	let p = Perspective::new(-2.0, 2.0, -2.0, 2.0, 2.0, 6.0).with_reversed_z(true);
+	assert!((project(&p, Vec3::new(0.0, 0.0, -2.0)).z - 1.0).abs() < EPSILON);
+	assert!(project(&p, Vec3::new(0.0, 0.0, -6.0)).z.abs() < EPSILON);
+	assert_close(project(&p, Vec3::new(1.0, 1.0, -3.0)), Vec3::new(1.0 / 3.0, 1.0 / 3.0, 0.5))
+}
+
+#[test]
+fn perspective_infinite_far() {
+	let p = Perspective::new(-2.0, 2.0, -2.0, 2.0, 2.0, 6.0).with_infinite_far();
+	assert!(project(&p, Vec3::new(0.0, 0.0, -2.0)).z.abs() < EPSILON);
+	assert!((project(&p, Vec3::new(0.0, 0.0, -1e6)).z - 1.0).abs() < 1e-3);
+
+	let p = p.with_reversed_z(true);
+	assert!((project(&p, Vec3::new(0.0, 0.0, -2.0)).z - 1.0).abs() < EPSILON);
+	assert!(project(&p, Vec3::new(0.0, 0.0, -1e6)).z.abs() < 1e-3);
+}
+
+#[test]
+fn perspective_aspect_ratio() {
+	let mut p = Perspective::fovx(std::f32::consts::FRAC_PI_2, 1.0, 2.0, 6.0);
+	p.resize(800, 400);
+	assert!((p.aspect_ratio() - 2.0).abs() < EPSILON);
+	assert!((p.horizontal_fov() - std::f32::consts::FRAC_PI_2).abs() < EPSILON);
+
+	let mut p = Perspective::fovy(std::f32::consts::FRAC_PI_2, 1.0, 2.0, 6.0);
+	p.set_aspect_ratio(2.0);
+	assert!((p.vertical_fov() - std::f32::consts::FRAC_PI_2).abs() < EPSILON);
+}
+
+#[test]
+fn orthographic() {
+	let p = Orthographic::new(-2.0, 2.0, -1.0, 1.0, 1.0, 5.0);
+	assert_close(project(&p, Vec3::new(2.0, 1.0, -1.0)), Vec3::new(1.0, 1.0, 0.0));
+	assert_close(project(&p, Vec3::new(-2.0, -1.0, -5.0)), Vec3::new(-1.0, -1.0, 1.0));
+
+	let p = p.with_reversed_z(true);
+	assert_close(project(&p, Vec3::new(2.0, 1.0, -1.0)), Vec3::new(1.0, 1.0, 1.0));
+	assert_close(project(&p, Vec3::new(-2.0, -1.0, -5.0)), Vec3::new(-1.0, -1.0, 0.0));
+}
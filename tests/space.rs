@@ -0,0 +1,109 @@
+//! Spatial index.
+use glam::Vec3;
+use scene::{
+	Scene,
+	Id
+};
+use engine::{
+	bounds::{
+		Aabb,
+		Sphere,
+		Ray
+	},
+	space::Index
+};
+
+struct Object;
+
+/// Unit box centered on the given point.
+fn unit_box(center: Vec3) -> Aabb {
+	Aabb::from_center(center, Vec3::splat(0.5))
+}
+
+/// Index of `n` unit boxes along the `x` axis, every 2 units.
+fn row(n: usize) -> (Vec<Id<Object>>, Index<Object>) {
+	let mut scene: Scene<Object, ()> = Scene::new();
+	let mut index = Index::new();
+	let objects: Vec<_> = (0..n).map(|i| {
+		let id = scene.insert(Object);
+		index.insert(id, unit_box(Vec3::new(2.0 * i as f32, 0.0, 0.0)));
+		id
+	}).collect();
+
+	(objects, index)
+}
+
+fn sorted(mut objects: Vec<Id<Object>>, all: &[Id<Object>]) -> Vec<usize> {
+	let mut indexes: Vec<_> = objects.drain(..).map(|id| all.iter().position(|o| *o == id).unwrap()).collect();
+	indexes.sort_unstable();
+	indexes
+}
+
+#[test]
+fn index_insert_remove() {
+	let (objects, mut index) = row(16);
+	assert_eq!(index.len(), 16);
+	assert!(objects.iter().all(|id| index.contains(*id)));
+	assert_eq!(index.get(objects[3]), Some(&unit_box(Vec3::new(6.0, 0.0, 0.0))));
+
+	assert_eq!(index.remove(objects[3]), Some(unit_box(Vec3::new(6.0, 0.0, 0.0))));
+	assert_eq!(index.remove(objects[3]), None);
+	assert!(!index.contains(objects[3]));
+	assert_eq!(index.len(), 15);
+
+	let mut found = Vec::new();
+	index.query_aabb(&Aabb::new(Vec3::splat(-100.0), Vec3::splat(100.0)), |id, _| found.push(id));
+	assert_eq!(found.len(), 15);
+	assert!(!found.contains(&objects[3]));
+
+	for id in &objects {
+		index.remove(*id);
+	}
+	assert!(index.is_empty())
+}
+
+#[test]
+fn index_update() {
+	let (objects, mut index) = row(8);
+
+	// Small movements fit in the margin.
+	assert!(!index.update(objects[0], unit_box(Vec3::new(0.05, 0.0, 0.0))));
+	assert!(index.update(objects[0], unit_box(Vec3::new(20.0, 0.0, 0.0))));
+
+	let mut found = Vec::new();
+	index.query_sphere(&Sphere::new(Vec3::new(20.0, 0.0, 0.0), 0.1), |id, _| found.push(id));
+	assert!(found == vec![objects[0]])
+}
+
+#[test]
+fn index_query() {
+	let (objects, index) = row(16);
+
+	let mut found = Vec::new();
+	index.query_aabb(&Aabb::new(Vec3::new(3.0, -1.0, -1.0), Vec3::new(7.0, 1.0, 1.0)), |id, _| found.push(id));
+	assert_eq!(sorted(found, &objects), vec![2, 3]);
+
+	let mut found = Vec::new();
+	index.query_sphere(&Sphere::new(Vec3::new(10.0, 2.0, 0.0), 1.6), |id, _| found.push(id));
+	assert_eq!(sorted(found, &objects), vec![5]);
+
+	let mut hits = Vec::new();
+	index.query_ray(&Ray::new(Vec3::new(-10.0, 0.0, 0.0), Vec3::unit_x()), 15.0, |id, d| hits.push((id, d)));
+	assert_eq!(sorted(hits.iter().map(|(id, _)| *id).collect(), &objects), vec![0, 1, 2]);
+	assert!(hits.iter().any(|(id, d)| *id == objects[0] && (*d - 9.5).abs() < 1e-5))
+}
+
+#[test]
+fn index_nearest() {
+	let (objects, index) = row(16);
+
+	let nearest = index.nearest(Vec3::new(9.2, 0.0, 0.0), 3);
+	assert_eq!(nearest.len(), 3);
+	assert_eq!(sorted(nearest.iter().map(|(id, _)| *id).collect(), &objects), vec![4, 5, 6]);
+	assert!(nearest.windows(2).all(|w| w[0].1 <= w[1].1));
+	assert!(nearest[0].0 == objects[5]);
+	assert!((nearest[0].1 - 0.3).abs() < 1e-5);
+
+	assert_eq!(index.nearest(Vec3::zero(), 100).len(), 16);
+	assert!(index.nearest(Vec3::zero(), 0).is_empty())
+}